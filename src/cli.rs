@@ -1,5 +1,6 @@
 use anyhow::{bail, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -20,6 +21,9 @@ pub struct Cli {
     /// Path to AGENTS.md (default: ./AGENTS.md)
     #[arg(long)]
     pub agents_path: Option<PathBuf>,
+    /// Ignore `.prime-agent.lock` and always recompute sync status from the files on disk
+    #[arg(long)]
+    pub no_lock: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -28,12 +32,23 @@ pub enum Command {
     Get {
         /// Skill names (comma-separated or space-separated)
         skills: Vec<String>,
+        /// Template variable override (key=value). Can be repeated.
+        #[arg(long = "var", value_name = "key=value")]
+        vars: Vec<String>,
+        /// Clear cached resolved template variables before rendering
+        #[arg(long)]
+        refresh_vars: bool,
     },
     /// Store a skill markdown file under skills/<name>.md
     Set {
         name: String,
         path: PathBuf,
     },
+    /// Fetch a skill from a configured registry or URL and store it locally
+    Add {
+        /// `<registry>/<skill>` or a raw http(s) URL
+        source: String,
+    },
     /// Sync skills with AGENTS.md
     Sync,
     /// Sync skills and pull remote changes
@@ -52,14 +67,81 @@ pub enum Command {
     },
     /// Remove a skill section from AGENTS.md
     Delete {
-        name: String,
+        /// Skill names (comma-separated or space-separated); omit or pass `-` to pick interactively
+        names: Vec<String>,
     },
     /// Remove a skill section and delete its markdown file
     DeleteGlobally {
+        /// Skill names (comma-separated or space-separated); omit or pass `-` to pick interactively
+        names: Vec<String>,
+    },
+    /// Manage the git pre-commit hook that runs sync automatically
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+    /// Render a skill's markdown to the terminal with syntax highlighting
+    Preview {
         name: String,
+        /// Disable syntax highlighting even on a TTY
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Show a unified diff for out-of-sync skills
+    Diff {
+        /// Only diff this skill (default: every out-of-sync skill)
+        name: Option<String>,
+    },
+    /// Drive an agent lifecycle plan through its gates until it completes or pauses for review
+    Run {
+        /// Path to the Markdown plan file
+        #[arg(long, default_value = "PLAN.md")]
+        plan: PathBuf,
+        /// Path to the lifecycle state JSON file
+        #[arg(long, default_value = "state.json")]
+        state: PathBuf,
+        /// Directory to run gates and commits in (default: `$HOME`, falling back to the current directory)
+        #[arg(long)]
+        workdir: Option<PathBuf>,
+        /// How to handle the repo's pre-commit hooks when the lifecycle commits
+        #[arg(long, value_enum, default_value = "run")]
+        hook_policy: HookPolicyArg,
+        /// Where to write the structured run report once the run stops
+        #[arg(long, default_value = "run-report.json")]
+        report: PathBuf,
+        /// Keep watching the workdir for file changes and re-run affected gates instead of
+        /// stopping once the plan completes
+        #[arg(long)]
+        watch: bool,
     },
 }
 
+/// How the lifecycle's final `git commit` should treat the repo's pre-commit hooks, mirrored from
+/// [`crate::lifecycle::HookPolicy`] so `cli` doesn't need to depend on the lifecycle module.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+#[value(rename_all = "kebab-case")]
+pub enum HookPolicyArg {
+    /// Let hooks run normally; a nonzero hook exit fails the commit
+    Run,
+    /// Skip hooks entirely via `--no-verify`
+    Skip,
+    /// Run hooks (output captured by the logger) and, if they reject the commit, retry once with
+    /// `--no-verify`
+    RunAndCapture,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HooksAction {
+    /// Install the pre-commit hook
+    Install {
+        /// Append to an existing unrelated pre-commit hook instead of refusing to install
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove the pre-commit hook block this tool installed
+    Uninstall,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ConfigAction {
     /// Set a configuration value
@@ -83,3 +165,117 @@ pub fn expand_skill_args(args: Vec<String>) -> Result<Vec<String>> {
     }
     Ok(names)
 }
+
+/// Parse repeated `--var key=value` flags into a map.
+///
+/// # Errors
+///
+/// Returns an error if a value is not in `key=value` form or has an empty key.
+pub fn parse_var_args(values: &[String]) -> Result<HashMap<String, String>> {
+    let mut vars = HashMap::new();
+    for value in values {
+        let Some((key, raw_value)) = value.split_once('=') else {
+            bail!("invalid --var value '{value}', expected key=value");
+        };
+        if key.trim().is_empty() {
+            bail!("invalid --var value '{value}', empty key");
+        }
+        vars.insert(key.trim().to_string(), raw_value.to_string());
+    }
+    Ok(vars)
+}
+
+/// Whether `args` should trigger the interactive picker: no names given, or a bare `-`.
+#[must_use]
+pub fn wants_interactive_picker(args: &[String]) -> bool {
+    args.is_empty() || (args.len() == 1 && args[0] == "-")
+}
+
+/// Upper bound on alias expansions per invocation, a depth guard alongside the cycle check below
+/// that caps how long a chain of distinct aliases may grow.
+const MAX_ALIAS_EXPANSIONS: usize = 32;
+
+/// Expand a user-defined alias in `argv` (the raw `std::env::args()` stream) the way cargo
+/// resolves `alias.*` entries: if the first non-flag token matches an alias key, splice the
+/// alias's tokens into argv in its place, then re-check the result for further aliases.
+///
+/// # Errors
+///
+/// Returns an error if an alias expands back into itself (directly or transitively), or if
+/// expansion exceeds [`MAX_ALIAS_EXPANSIONS`] steps.
+pub fn expand_aliases(argv: Vec<String>, aliases: &HashMap<String, Vec<String>>) -> Result<Vec<String>> {
+    let builtins = builtin_subcommand_names();
+    let mut argv = argv;
+    let mut already_expanded = HashSet::new();
+
+    for _ in 0..MAX_ALIAS_EXPANSIONS {
+        let Some(index) = argv.iter().skip(1).position(|arg| !arg.starts_with('-')).map(|pos| pos + 1) else {
+            return Ok(argv);
+        };
+        let token = argv[index].clone();
+        if builtins.contains(&token) {
+            return Ok(argv);
+        }
+        let Some(replacement) = aliases.get(&token) else {
+            return Ok(argv);
+        };
+        if !already_expanded.insert(token.clone()) {
+            bail!("alias cycle detected while expanding '{token}'");
+        }
+
+        let mut expanded = argv[..index].to_vec();
+        expanded.extend(replacement.iter().cloned());
+        expanded.extend(argv[index + 1..].iter().cloned());
+        argv = expanded;
+    }
+    bail!("alias expansion exceeded {MAX_ALIAS_EXPANSIONS} steps; check for overly long alias chains");
+}
+
+fn builtin_subcommand_names() -> HashSet<String> {
+    Cli::command()
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn expands_single_token_alias() {
+        let mut aliases = HashMap::new();
+        aliases.insert("g".to_string(), vec!["get".to_string()]);
+        let expanded = expand_aliases(argv(&["prime-agent", "g", "alpha"]), &aliases).expect("expand");
+        assert_eq!(expanded, argv(&["prime-agent", "get", "alpha"]));
+    }
+
+    #[test]
+    fn never_shadows_a_builtin_subcommand() {
+        let mut aliases = HashMap::new();
+        aliases.insert("get".to_string(), vec!["list".to_string()]);
+        let expanded = expand_aliases(argv(&["prime-agent", "get", "alpha"]), &aliases).expect("expand");
+        assert_eq!(expanded, argv(&["prime-agent", "get", "alpha"]));
+    }
+
+    #[test]
+    fn rejects_alias_cycles() {
+        let mut aliases = HashMap::new();
+        aliases.insert("pull".to_string(), vec!["fetch".to_string()]);
+        aliases.insert("fetch".to_string(), vec!["pull".to_string()]);
+        let result = expand_aliases(argv(&["prime-agent", "pull"]), &aliases);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn expands_multi_token_alias() {
+        let mut aliases = HashMap::new();
+        aliases.insert("refresh".to_string(), vec!["sync".to_string(), "--some-flag".to_string()]);
+        let expanded = expand_aliases(argv(&["prime-agent", "refresh"]), &aliases).expect("expand");
+        assert_eq!(expanded, argv(&["prime-agent", "sync", "--some-flag"]));
+    }
+}
@@ -1,13 +1,13 @@
 use anyhow::{Context, Result};
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Runner configuration loaded from JSON.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
-    #[serde(rename = "cli-program")]
+    #[serde(rename = "cli-program", default = "default_cli_program")]
     pub cli_program: String,
     #[serde(rename = "tool-type")]
     pub tool_type: Option<ToolType>,
@@ -19,15 +19,82 @@ pub struct Config {
     pub lifecycles: HashMap<String, LifecycleConfig>,
     #[serde(default)]
     pub gates: Vec<GateCommand>,
+    /// Named skill registries, mapping a short name to a base URL or git remote.
+    #[serde(default)]
+    pub registries: HashMap<String, String>,
+    /// User-defined command shortcuts, e.g. `"g": ["get"]` or `"pull": "sync-remote"`.
+    #[serde(default, deserialize_with = "deserialize_aliases")]
+    pub aliases: HashMap<String, Vec<String>>,
+    /// Directory containing skill markdown files; the one required setting for the skills CLI.
+    #[serde(rename = "skills-dir", default)]
+    pub skills_dir: Option<String>,
+    /// Base URL of an HTTP skill registry `sync-remote` pushes to/pulls from. When set, it
+    /// replaces the git remote as the source of truth; unset falls back to `git push`/`pull`.
+    #[serde(rename = "registry-url", default)]
+    pub registry_url: Option<String>,
+    /// Bearer token authenticating requests to `registry-url`.
+    #[serde(rename = "registry-token", default)]
+    pub registry_token: Option<String>,
+    /// Run independent gates concurrently instead of strictly in declaration order.
+    #[serde(rename = "gates-parallel", default)]
+    pub gates_parallel: bool,
+    /// Cap on concurrently-running gates when `gates-parallel` is set; defaults to available CPUs.
+    #[serde(rename = "gates-max-parallel", default)]
+    pub gates_max_parallel: Option<usize>,
+    /// Directory [`crate::logging::Logger`] writes its log file (and rotations) to; defaults to
+    /// `/tmp/prime-agent`.
+    #[serde(rename = "log-dir", default)]
+    pub log_dir: Option<String>,
+    /// Rotate the log file once it exceeds this many bytes.
+    #[serde(rename = "log-max-bytes", default)]
+    pub log_max_bytes: Option<u64>,
+    /// Number of rotated log files to keep before dropping the oldest.
+    #[serde(rename = "log-keep-rotations", default)]
+    pub log_keep_rotations: Option<usize>,
+    /// Emit each log line as a JSON object instead of plain text.
+    #[serde(rename = "log-json", default)]
+    pub log_json: Option<bool>,
+    /// Any other `key: value` setting not otherwise modeled, e.g. `owner`, `theme`, `finder-program`.
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+
+fn default_cli_program() -> String {
+    "cursor-agent".to_string()
+}
+
+/// Accept either a single whitespace-split string or a JSON list of tokens per alias.
+fn deserialize_aliases<'de, D>(deserializer: D) -> Result<HashMap<String, Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AliasValue {
+        Single(String),
+        Tokens(Vec<String>),
+    }
+
+    let raw: HashMap<String, AliasValue> = HashMap::deserialize(deserializer)?;
+    Ok(raw
+        .into_iter()
+        .map(|(name, value)| {
+            let tokens = match value {
+                AliasValue::Single(command) => command.split_whitespace().map(str::to_string).collect(),
+                AliasValue::Tokens(tokens) => tokens,
+            };
+            (name, tokens)
+        })
+        .collect())
 }
 
 /// Configuration for a specific lifecycle.
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct LifecycleConfig {
     pub model: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "kebab-case")]
 pub enum ToolType {
     Cursor,
@@ -35,12 +102,45 @@ pub enum ToolType {
 }
 
 /// Command definition for a gating step.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GateCommand {
     pub name: Option<String>,
     pub command: String,
     #[serde(default)]
     pub args: Vec<String>,
+    /// Override `command`/`args` when running on Windows (e.g. a `.cmd` shim that takes
+    /// different flags). Falls back to `command`/`args` when unset.
+    #[serde(default)]
+    pub windows: Option<PlatformOverride>,
+    /// Never run this gate concurrently with others, e.g. two gates that both write the same
+    /// target directory. Ignored unless `gates-parallel` is set.
+    #[serde(default)]
+    pub sequential: bool,
+    /// An exit code that should pause the lifecycle for human review rather than being stored as
+    /// a hard failure, e.g. a linter's dedicated "warnings only" status.
+    #[serde(rename = "unstable-exit-code", default)]
+    pub unstable_exit_code: Option<i32>,
+}
+
+impl GateCommand {
+    /// Resolve the `(command, args)` this gate should run with on the current platform.
+    #[must_use]
+    pub fn resolve(&self) -> (&str, &[String]) {
+        if cfg!(windows) {
+            if let Some(override_) = &self.windows {
+                return (override_.command.as_str(), &override_.args);
+            }
+        }
+        (self.command.as_str(), &self.args)
+    }
+}
+
+/// A platform-specific `command`/`args` override, e.g. under a gate's `windows` key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlatformOverride {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
 }
 
 impl Config {
@@ -108,10 +208,258 @@ impl Config {
             }
         }
 
-        programs
+        if cfg!(windows) {
+            expand_for_windows(&programs)
+        } else {
+            programs
+        }
+    }
+
+    /// Load configuration from a JSON file, requiring it to already exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load_required(path: &Path) -> Result<Self> {
+        Self::load(path)
+    }
+
+    /// Load configuration from a JSON file, falling back to defaults if it doesn't exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        Self::load_optional(Some(path).filter(|path| path.exists()))
+    }
+
+    /// Persist this configuration as JSON to `path`, creating parent directories as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be serialized or written.
+    pub fn save_to_path(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create config dir: {}", parent.display()))?;
+        }
+        let contents = serde_json::to_string_pretty(self).context("failed to serialize config")?;
+        fs::write(path, contents).with_context(|| format!("failed to write config: {}", path.display()))
+    }
+
+    /// The configured skills directory, if set.
+    #[must_use]
+    pub fn skills_dir(&self) -> Option<PathBuf> {
+        self.skills_dir.clone().map(PathBuf::from)
+    }
+
+    /// Look up a single configuration value by its on-disk key (e.g. `"skills-dir"`, `"owner"`).
+    #[must_use]
+    pub fn get_value(&self, key: &str) -> Option<String> {
+        match key {
+            "skills-dir" => self.skills_dir.clone(),
+            "registry-url" => self.registry_url.clone(),
+            "registry-token" => self.registry_token.clone(),
+            "log-dir" => self.log_dir.clone(),
+            "log-max-bytes" => self.log_max_bytes.map(|value| value.to_string()),
+            "log-keep-rotations" => self.log_keep_rotations.map(|value| value.to_string()),
+            "log-json" => self.log_json.map(|value| value.to_string()),
+            _ => self.extra.get(key).cloned(),
+        }
+    }
+
+    /// Set a single configuration value by its on-disk key.
+    pub fn set_value(&mut self, key: &str, value: &str) {
+        match key {
+            "skills-dir" => self.skills_dir = Some(value.to_string()),
+            "registry-url" => self.registry_url = Some(value.to_string()),
+            "registry-token" => self.registry_token = Some(value.to_string()),
+            "log-dir" => self.log_dir = Some(value.to_string()),
+            "log-max-bytes" => self.log_max_bytes = value.parse().ok(),
+            "log-keep-rotations" => self.log_keep_rotations = value.parse().ok(),
+            "log-json" => self.log_json = value.parse().ok(),
+            _ => {
+                self.extra.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+
+    /// Apply a batch of `key: value` overrides, e.g. parsed `--config key:value` flags.
+    pub fn apply_overrides(&mut self, overrides: &HashMap<String, String>) {
+        for (key, value) in overrides {
+            self.set_value(key, value);
+        }
+    }
+
+    /// All configuration values as a sorted `key -> value` map, for display.
+    #[must_use]
+    pub fn all_values(&self) -> BTreeMap<String, String> {
+        let mut values: BTreeMap<String, String> =
+            self.extra.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+        if let Some(dir) = &self.skills_dir {
+            values.insert("skills-dir".to_string(), dir.clone());
+        }
+        if let Some(url) = &self.registry_url {
+            values.insert("registry-url".to_string(), url.clone());
+        }
+        if let Some(token) = &self.registry_token {
+            values.insert("registry-token".to_string(), token.clone());
+        }
+        if let Some(dir) = &self.log_dir {
+            values.insert("log-dir".to_string(), dir.clone());
+        }
+        if let Some(max_bytes) = self.log_max_bytes {
+            values.insert("log-max-bytes".to_string(), max_bytes.to_string());
+        }
+        if let Some(keep_rotations) = self.log_keep_rotations {
+            values.insert("log-keep-rotations".to_string(), keep_rotations.to_string());
+        }
+        if let Some(json) = self.log_json {
+            values.insert("log-json".to_string(), json.to_string());
+        }
+        values
+    }
+
+    /// Resolve configuration the way cargo resolves `.cargo/config.toml`: start from the global
+    /// config file, then merge in every `.prime-agent.toml` found walking up from `cwd` to the
+    /// filesystem root, applied shallowest-first so a file closer to `cwd` wins, mirroring how
+    /// `cargo` resolves `.cargo/config.toml` up the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any layer exists but cannot be read or parsed.
+    pub fn discover(cwd: &Path) -> Result<Self> {
+        let mut merged = Self::default();
+
+        if let Ok(global_path) = config_path() {
+            if global_path.exists() {
+                merged.merge(&Self::load(&global_path)?);
+            }
+        }
+        for layer_path in discover_local_layers(cwd) {
+            merged.merge(&load_toml_layer(&layer_path)?);
+        }
+
+        Ok(merged)
+    }
+
+    /// Apply every key `other` has explicitly set onto `self`, leaving any field `other` left at
+    /// its default untouched. Maps/aliases/registries merge key-by-key rather than replacing the
+    /// whole collection, so a closer layer can add or override a single entry without dropping the
+    /// rest; everything else (scalars, `Option`s, gate lists) is a straight override.
+    pub fn merge(&mut self, other: &Config) {
+        let default = Config::default();
+
+        if other.cli_program != default.cli_program {
+            self.cli_program = other.cli_program.clone();
+        }
+        if other.tool_type.is_some() {
+            self.tool_type = other.tool_type;
+        }
+        for (tool_type, path) in &other.tool_paths {
+            self.tool_paths.insert(*tool_type, path.clone());
+        }
+        if !other.cli_args.is_empty() {
+            self.cli_args = other.cli_args.clone();
+        }
+        for (stage, lifecycle_config) in &other.lifecycles {
+            self.lifecycles.insert(stage.clone(), LifecycleConfig { model: lifecycle_config.model.clone() });
+        }
+        if !other.gates.is_empty() {
+            self.gates = other.gates.clone();
+        }
+        for (name, url) in &other.registries {
+            self.registries.insert(name.clone(), url.clone());
+        }
+        for (alias, tokens) in &other.aliases {
+            self.aliases.insert(alias.clone(), tokens.clone());
+        }
+        if other.skills_dir.is_some() {
+            self.skills_dir = other.skills_dir.clone();
+        }
+        if other.registry_url.is_some() {
+            self.registry_url = other.registry_url.clone();
+        }
+        if other.registry_token.is_some() {
+            self.registry_token = other.registry_token.clone();
+        }
+        if other.gates_parallel {
+            self.gates_parallel = true;
+        }
+        if other.gates_max_parallel.is_some() {
+            self.gates_max_parallel = other.gates_max_parallel;
+        }
+        if other.log_dir.is_some() {
+            self.log_dir = other.log_dir.clone();
+        }
+        if other.log_max_bytes.is_some() {
+            self.log_max_bytes = other.log_max_bytes;
+        }
+        if other.log_keep_rotations.is_some() {
+            self.log_keep_rotations = other.log_keep_rotations;
+        }
+        if other.log_json.is_some() {
+            self.log_json = other.log_json;
+        }
+        for (key, value) in &other.extra {
+            self.extra.insert(key.clone(), value.clone());
+        }
+    }
+
+    /// Max number of gates to run concurrently when `gates-parallel` is set: the configured
+    /// `gates-max-parallel` if present, otherwise the number of available CPUs (falling back to
+    /// 4 if that can't be determined).
+    #[must_use]
+    pub fn gate_parallelism(&self) -> usize {
+        self.gates_max_parallel.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(std::num::NonZeroUsize::get)
+                .unwrap_or(4)
+        })
     }
 }
 
+/// Collect every `.prime-agent.toml` found walking from `start_dir` up to the filesystem root,
+/// ordered shallowest (closest to root) first so later layers can override earlier ones.
+fn discover_local_layers(start_dir: &Path) -> Vec<PathBuf> {
+    let mut layers = Vec::new();
+    let mut dir = Some(start_dir.to_path_buf());
+    while let Some(current) = dir {
+        let candidate = current.join(".prime-agent.toml");
+        if candidate.is_file() {
+            layers.push(candidate);
+        }
+        dir = current.parent().map(Path::to_path_buf);
+    }
+    layers.reverse();
+    layers
+}
+
+/// Parse a `.prime-agent.toml` layer as a [`Config`], via the same JSON representation `Config`'s
+/// `Deserialize` impl is derived for.
+fn load_toml_layer(path: &Path) -> Result<Config> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read config layer: {}", path.display()))?;
+    let value: toml::Value = toml::from_str(&contents)
+        .with_context(|| format!("failed to parse config layer as TOML: {}", path.display()))?;
+    let json = serde_json::to_value(value)
+        .with_context(|| format!("failed to normalize config layer: {}", path.display()))?;
+    serde_json::from_value(json).with_context(|| format!("failed to parse config layer: {}", path.display()))
+}
+
+/// `Command::new` on Windows doesn't consult `PATHEXT` the way a shell does, so a bare name like
+/// `cursor-agent` silently fails to find `cursor-agent.cmd`/`.bat` shims on PATH. Try each
+/// candidate's bare form first, then its `.cmd`/`.bat` variants, preserving relative order.
+fn expand_for_windows(programs: &[String]) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(programs.len() * 3);
+    for program in programs {
+        expanded.push(program.clone());
+        expanded.push(format!("{program}.cmd"));
+        expanded.push(format!("{program}.bat"));
+    }
+    expanded
+}
+
 fn push_unique(programs: &mut Vec<String>, value: &str) {
     if !programs.iter().any(|existing| existing == value) {
         programs.push(value.to_string());
@@ -121,16 +469,58 @@ fn push_unique(programs: &mut Vec<String>, value: &str) {
 impl Default for Config {
     fn default() -> Self {
         Self {
-            cli_program: "cursor-agent".to_string(),
+            cli_program: default_cli_program(),
             tool_type: Some(ToolType::Cursor),
             tool_paths: HashMap::new(),
             cli_args: Vec::new(),
             lifecycles: HashMap::new(),
             gates: Vec::new(),
+            registries: HashMap::new(),
+            aliases: HashMap::new(),
+            skills_dir: None,
+            registry_url: None,
+            registry_token: None,
+            gates_parallel: false,
+            gates_max_parallel: None,
+            log_dir: None,
+            log_max_bytes: None,
+            log_keep_rotations: None,
+            log_json: None,
+            extra: HashMap::new(),
         }
     }
 }
 
+/// Resolve the global config file path: `$XDG_CONFIG_HOME/prime-agent/config`, falling back to
+/// `$HOME/.config/prime-agent/config`.
+///
+/// # Errors
+///
+/// Returns an error if neither `XDG_CONFIG_HOME` nor `HOME` is set.
+pub fn config_path() -> Result<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .context("could not determine config directory; set XDG_CONFIG_HOME or HOME")?;
+    Ok(base.join("prime-agent").join("config"))
+}
+
+/// Ensure the config file (and its parent directory) exists, creating an empty `{}` file if not.
+///
+/// # Errors
+///
+/// Returns an error if the directory or file cannot be created.
+pub fn ensure_config_file(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create config dir: {}", parent.display()))?;
+    }
+    if !path.exists() {
+        fs::write(path, "{}\n").with_context(|| format!("failed to create config: {}", path.display()))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +553,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn aliases_accept_string_or_list_form() {
+        let json = r#"{
+            "cli-program": "cursor",
+            "aliases": { "pull": "sync-remote", "g": ["get"] }
+        }"#;
+        let config: Config = serde_json::from_str(json).expect("valid config");
+        assert_eq!(config.aliases.get("pull"), Some(&vec!["sync-remote".to_string()]));
+        assert_eq!(config.aliases.get("g"), Some(&vec!["get".to_string()]));
+    }
+
     #[test]
     fn default_config_uses_cursor_agent() {
         let config = Config::default();
@@ -173,4 +574,82 @@ mod tests {
                 .any(|program| program == "cursor-agent")
         );
     }
+
+    #[test]
+    fn expands_candidates_with_windows_shim_extensions() {
+        let programs = vec!["cursor-agent".to_string(), "agent".to_string()];
+        assert_eq!(
+            expand_for_windows(&programs),
+            vec![
+                "cursor-agent".to_string(),
+                "cursor-agent.cmd".to_string(),
+                "cursor-agent.bat".to_string(),
+                "agent".to_string(),
+                "agent.cmd".to_string(),
+                "agent.bat".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn gate_command_falls_back_without_windows_override() {
+        let gate = GateCommand {
+            name: None,
+            command: "cargo".to_string(),
+            args: vec!["build".to_string()],
+            windows: None,
+            sequential: false,
+            unstable_exit_code: None,
+        };
+        assert_eq!(gate.resolve(), ("cargo", &["build".to_string()][..]));
+    }
+
+    #[test]
+    fn gate_parallelism_uses_configured_cap() {
+        let mut config = Config::default();
+        config.gates_max_parallel = Some(2);
+        assert_eq!(config.gate_parallelism(), 2);
+    }
+
+    #[test]
+    fn layered_discovery_prefers_deeper_directories() {
+        let root = tempfile::tempdir().expect("temp dir");
+        fs::write(
+            root.path().join(".prime-agent.toml"),
+            "skills-dir = \"/root/skills\"\nowner = \"root-team\"\n",
+        )
+        .expect("write root layer");
+
+        let nested = root.path().join("nested");
+        fs::create_dir_all(&nested).expect("create nested dir");
+        fs::write(nested.join(".prime-agent.toml"), "skills-dir = \"/nested/skills\"\n")
+            .expect("write nested layer");
+
+        std::env::set_var("XDG_CONFIG_HOME", root.path().join("no-such-config-home"));
+        let config = Config::discover(&nested).expect("discover layered config");
+        std::env::remove_var("XDG_CONFIG_HOME");
+
+        assert_eq!(config.skills_dir, Some("/nested/skills".to_string()));
+        assert_eq!(config.extra.get("owner"), Some(&"root-team".to_string()));
+    }
+
+    #[test]
+    fn merge_only_overrides_keys_other_has_set() {
+        let mut base = Config::default();
+        base.cli_program = "base-agent".to_string();
+        base.skills_dir = Some("/base/skills".to_string());
+        base.extra.insert("owner".to_string(), "base-team".to_string());
+
+        let mut overlay = Config::default();
+        overlay.registry_url = Some("https://registry.example".to_string());
+        overlay.extra.insert("theme".to_string(), "dark".to_string());
+
+        base.merge(&overlay);
+
+        assert_eq!(base.cli_program, "base-agent");
+        assert_eq!(base.skills_dir, Some("/base/skills".to_string()));
+        assert_eq!(base.registry_url, Some("https://registry.example".to_string()));
+        assert_eq!(base.extra.get("owner"), Some(&"base-team".to_string()));
+        assert_eq!(base.extra.get("theme"), Some(&"dark".to_string()));
+    }
 }
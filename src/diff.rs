@@ -0,0 +1,57 @@
+use crate::agents_md::AgentsDoc;
+use crate::skills_store::SkillsStore;
+use crate::sync::{self, SyncStatus};
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Print a unified diff between the stored skill and the AGENTS.md section for every out-of-sync
+/// skill, or only `name` if given. Turns the opaque `Local`/`Remote`/`Conflict` labels `local`
+/// prints into something actionable.
+///
+/// # Errors
+///
+/// Returns an error if AGENTS.md or a skill file exists but cannot be read.
+pub fn run_diff(skills_store: &SkillsStore, agents_path: &Path, name: Option<&str>) -> Result<()> {
+    let agents_doc = if agents_path.exists() {
+        let contents = fs::read_to_string(agents_path)
+            .with_context(|| format!("failed to read '{}'", agents_path.display()))?;
+        Some(AgentsDoc::parse(&contents)?)
+    } else {
+        None
+    };
+    let statuses = sync::compute_sync_status(skills_store, agents_doc.as_ref())?;
+    let color = std::io::stdout().is_terminal();
+
+    let mut printed_any = false;
+    for (section_name, status) in statuses {
+        if status == SyncStatus::InSync {
+            continue;
+        }
+        if name.is_some_and(|filter| filter != section_name) {
+            continue;
+        }
+
+        let skill_content = if skills_store.skill_exists(&section_name) {
+            skills_store.load_skill(&section_name)?
+        } else {
+            String::new()
+        };
+        let agents_content = agents_doc
+            .as_ref()
+            .and_then(|doc| doc.get_section(&section_name))
+            .map(|section| section.content_string())
+            .unwrap_or_default();
+
+        println!("--- {section_name} (skill)");
+        println!("+++ {section_name} (agents.md, {status:?})");
+        print!("{}", sync::render_diff(&skill_content, &agents_content, color));
+        printed_any = true;
+    }
+
+    if !printed_any {
+        println!("no out-of-sync skills");
+    }
+    Ok(())
+}
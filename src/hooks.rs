@@ -0,0 +1,167 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+const BEGIN_MARKER: &str = "# >>> prime-agent sync hook >>>";
+const END_MARKER: &str = "# <<< prime-agent sync hook <<<";
+
+/// Install a `pre-commit` hook that runs `prime-agent sync` before each commit, aborting the
+/// commit if sync exits non-zero.
+///
+/// # Errors
+///
+/// Returns an error if `.git/hooks` is missing, or an existing unrelated hook is present and
+/// `force` is `false`.
+pub fn install(repo_root: &Path, skills_dir: &Path, agents_path: &Path, force: bool) -> Result<()> {
+    let hook_path = hook_path(repo_root)?;
+    let block = hook_block(skills_dir, agents_path);
+
+    if hook_path.exists() {
+        let existing = fs::read_to_string(&hook_path)
+            .with_context(|| format!("failed to read existing hook '{}'", hook_path.display()))?;
+        if existing.contains(BEGIN_MARKER) {
+            let replaced = replace_block(&existing, &block);
+            write_hook(&hook_path, &replaced)?;
+            return Ok(());
+        }
+        if !force {
+            bail!(
+                "refusing to overwrite existing pre-commit hook at '{}' (pass --force to append)",
+                hook_path.display()
+            );
+        }
+        let appended = format!("{}\n{}\n", existing.trim_end(), block);
+        write_hook(&hook_path, &appended)?;
+        return Ok(());
+    }
+
+    let script = format!("#!/bin/sh\n{block}\n");
+    write_hook(&hook_path, &script)?;
+    Ok(())
+}
+
+/// Remove only the block this tool previously wrote, leaving any other hook content intact.
+///
+/// # Errors
+///
+/// Returns an error if the hook file cannot be read or written.
+pub fn uninstall(repo_root: &Path) -> Result<()> {
+    let hook_path = hook_path(repo_root)?;
+    if !hook_path.exists() {
+        return Ok(());
+    }
+    let existing = fs::read_to_string(&hook_path)
+        .with_context(|| format!("failed to read hook '{}'", hook_path.display()))?;
+    if !existing.contains(BEGIN_MARKER) {
+        return Ok(());
+    }
+    let without_block = remove_block(&existing);
+    let remaining = without_block.trim();
+    if remaining.is_empty() || remaining == "#!/bin/sh" {
+        fs::remove_file(&hook_path)
+            .with_context(|| format!("failed to remove hook '{}'", hook_path.display()))?;
+    } else {
+        write_hook(&hook_path, &without_block)?;
+    }
+    Ok(())
+}
+
+fn hook_path(repo_root: &Path) -> Result<PathBuf> {
+    let hooks_dir = repo_root.join(".git").join("hooks");
+    if !hooks_dir.is_dir() {
+        bail!("'{}' is not a git hooks directory", hooks_dir.display());
+    }
+    Ok(hooks_dir.join("pre-commit"))
+}
+
+fn hook_block(skills_dir: &Path, agents_path: &Path) -> String {
+    format!(
+        "{BEGIN_MARKER}\n\
+if ! prime-agent sync --skills-dir \"{}\" --agents-path \"{}\"; then\n\
+    echo \"prime-agent sync failed; aborting commit\" >&2\n\
+    exit 1\n\
+fi\n\
+{END_MARKER}",
+        skills_dir.display(),
+        agents_path.display()
+    )
+}
+
+fn replace_block(existing: &str, block: &str) -> String {
+    let Some(start) = existing.find(BEGIN_MARKER) else {
+        return existing.to_string();
+    };
+    let Some(end_offset) = existing[start..].find(END_MARKER) else {
+        return existing.to_string();
+    };
+    let end = start + end_offset + END_MARKER.len();
+    format!("{}{}{}", &existing[..start], block, &existing[end..])
+}
+
+fn remove_block(existing: &str) -> String {
+    let Some(start) = existing.find(BEGIN_MARKER) else {
+        return existing.to_string();
+    };
+    let Some(end_offset) = existing[start..].find(END_MARKER) else {
+        return existing.to_string();
+    };
+    let end = start + end_offset + END_MARKER.len();
+    let mut result = existing[..start].to_string();
+    result.push_str(existing[end..].trim_start_matches('\n'));
+    result
+}
+
+fn write_hook(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("failed to write hook '{}'", path.display()))?;
+    let mut permissions = fs::metadata(path)
+        .with_context(|| format!("failed to stat hook '{}'", path.display()))?
+        .permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(path, permissions)
+        .with_context(|| format!("failed to make hook executable: '{}'", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo() -> tempfile::TempDir {
+        let temp = tempfile::tempdir().expect("temp dir");
+        fs::create_dir_all(temp.path().join(".git/hooks")).expect("hooks dir");
+        temp
+    }
+
+    #[test]
+    fn installs_fresh_hook() {
+        let temp = init_repo();
+        install(temp.path(), Path::new("skills"), Path::new("AGENTS.md"), false).expect("install");
+        let contents = fs::read_to_string(temp.path().join(".git/hooks/pre-commit")).expect("hook");
+        assert!(contents.contains(BEGIN_MARKER));
+        assert!(contents.contains("prime-agent sync"));
+    }
+
+    #[test]
+    fn refuses_to_clobber_unrelated_hook_without_force() {
+        let temp = init_repo();
+        fs::write(temp.path().join(".git/hooks/pre-commit"), "#!/bin/sh\necho custom\n").expect("hook");
+        let result = install(temp.path(), Path::new("skills"), Path::new("AGENTS.md"), false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn uninstall_removes_only_our_block() {
+        let temp = init_repo();
+        fs::write(
+            temp.path().join(".git/hooks/pre-commit"),
+            "#!/bin/sh\necho custom\n",
+        )
+        .expect("hook");
+        install(temp.path(), Path::new("skills"), Path::new("AGENTS.md"), true).expect("install");
+        uninstall(temp.path()).expect("uninstall");
+        let contents = fs::read_to_string(temp.path().join(".git/hooks/pre-commit")).expect("hook");
+        assert!(!contents.contains(BEGIN_MARKER));
+        assert!(contents.contains("echo custom"));
+    }
+}
@@ -1,14 +1,18 @@
-use anyhow::{Context, Result, anyhow};
-use indicatif::{ProgressBar, ProgressStyle};
-use std::fs::File;
+use anyhow::{Context, Result, anyhow, bail};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::config::{Config, GateCommand, ToolType};
 use crate::logging::Logger;
+use crate::patch;
 use crate::plan::{Plan, PlanStep};
+use crate::report::{self, RunReport};
 use crate::state::{StateFile, StepState};
 use crate::steps::StepsFile;
 
@@ -17,6 +21,46 @@ pub struct RunOptions {
     pub plan_path: PathBuf,
     pub state_path: PathBuf,
     pub workdir: PathBuf,
+    pub hook_policy: HookPolicy,
+    /// Where [`run_to_completion`] writes the accumulated [`RunReport`] once the run stops.
+    pub report_path: PathBuf,
+}
+
+impl RunOptions {
+    /// Construct options for a lifecycle run, resolving `workdir` via [`resolve_workdir`] (falling
+    /// back to `$HOME`/the current directory, then confirming the result is a git work tree).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `resolve_workdir` can't find or confirm a usable working directory.
+    pub fn new(
+        plan_path: PathBuf,
+        state_path: PathBuf,
+        workdir: Option<&Path>,
+        hook_policy: HookPolicy,
+        report_path: PathBuf,
+    ) -> Result<Self> {
+        Ok(Self {
+            plan_path,
+            state_path,
+            workdir: resolve_workdir(workdir)?,
+            hook_policy,
+            report_path,
+        })
+    }
+}
+
+/// How the lifecycle's final `git commit` should treat the repo's pre-commit hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HookPolicy {
+    /// Let hooks run normally; a nonzero hook exit fails the commit.
+    #[default]
+    Run,
+    /// Skip hooks entirely via `--no-verify`, e.g. in CI where hooks duplicate the gates already run.
+    Skip,
+    /// Run hooks (output captured through the existing `logger`) and, if they reject the commit,
+    /// retry once with `--no-verify` so an unrelated hook failure never blocks the agent.
+    RunAndCapture,
 }
 
 pub struct NextAction<'a> {
@@ -63,7 +107,10 @@ pub fn next_action<'a>(
                     lifecycle: lifecycle_stage,
                 }));
             }
-            StepState::ImplementedCommitted => {}
+            // A paused step is skipped so it waits for a human to look it over; the
+            // `lifecycle_override` path above only matches a stage's pre-state, so it skips
+            // paused steps too.
+            StepState::ImplementedCommitted | StepState::Paused(_) => {}
         }
     }
 
@@ -72,9 +119,12 @@ pub fn next_action<'a>(
 
 /// Execute a single lifecycle step and update state.
 ///
+/// Returns `Ok(true)` if the step advanced to its next state, or `Ok(false)` if a gate hit its
+/// configured `unstable_exit_code` and the step was left `Paused` for human review instead.
+///
 /// # Errors
 ///
-/// Returns an error if the agent action or gating commands fail.
+/// Returns an error if the agent action or gating commands fail outright (as opposed to pausing).
 pub fn run_lifecycle(
     config: &Config,
     plan: &Plan,
@@ -83,10 +133,15 @@ pub fn run_lifecycle(
     logger: &Logger,
     step: &PlanStep,
     lifecycle: u8,
+    report: &mut RunReport,
 ) -> Result<bool> {
+    let started = Instant::now();
     let (current_state, next_state, action_label) = lifecycle_mapping(lifecycle)?;
     let step_state = state.state_for(&step.id);
-    if step_state != current_state && step_state != StepState::LifecycleError(lifecycle) {
+    if step_state != current_state
+        && step_state != StepState::LifecycleError(lifecycle)
+        && step_state != StepState::Paused(lifecycle)
+    {
         return Err(anyhow!(
             "step {} in state '{}' cannot run lifecycle {}",
             step.id,
@@ -135,9 +190,19 @@ pub fn run_lifecycle(
         None
     };
 
+    let mut gate_records: Vec<report::GateRecord> = Vec::new();
+
     let execution_result = if lifecycle == 5 {
-        run_gates(config, options, logger)
+        run_gates(config, options, logger, &mut gate_records)
             .and_then(|()| run_git_commit(step, options, logger, lifecycle))
+            .map(|commit_info| {
+                if let Some(info) = commit_info {
+                    logger.log_substep(&format!(
+                        "Lifecycle {} committed as {} ({})",
+                        info.lifecycle, info.hash, info.date
+                    ));
+                }
+            })
             .with_context(|| format!("lifecycle {lifecycle}: git commit failed"))
     } else {
         let action = ActionContext {
@@ -149,7 +214,7 @@ pub fn run_lifecycle(
             resume_prompt,
         };
         run_cli_action(config, options, logger, &action)
-            .and_then(|()| run_gates(config, options, logger))
+            .and_then(|()| run_gates(config, options, logger, &mut gate_records))
             .with_context(|| format!("lifecycle {lifecycle}: agent action failed"))
     }
     .with_context(|| {
@@ -159,7 +224,35 @@ pub fn run_lifecycle(
         )
     });
 
+    // The exit code of the last gate that actually failed. A nonzero code goes through
+    // `StepState::from_stage_result`, which yields the same `LifecycleError(lifecycle)` as the
+    // fallback; the code itself is kept only in the gate records of the report.
+    let failing_exit_code = gate_records
+        .iter()
+        .rev()
+        .find(|record| !record.success)
+        .and_then(|record| record.exit_code);
+
+    report.push_step(report::StepRecord {
+        step_id: step.id.clone(),
+        step_number: step.number,
+        lifecycle,
+        action: action_label.to_string(),
+        success: execution_result.is_ok(),
+        duration_ms: started.elapsed().as_millis(),
+        gates: gate_records,
+    });
+
     if let Err(err) = execution_result {
+        if err.downcast_ref::<UnstableGate>().is_some() {
+            logger.log_step(&format!("Lifecycle {lifecycle} paused for human review: {err}"));
+            state.set_state(&step.id, StepState::Paused(lifecycle));
+            if let Err(save_err) = state.save(&options.state_path) {
+                logger.log_error(&format!("Failed to save paused state: {save_err}"));
+            }
+            return Ok(false);
+        }
+
         let details = vec![
             format!("Lifecycle: {}", lifecycle),
             format!("Action: {}", action_label),
@@ -169,7 +262,11 @@ pub fn run_lifecycle(
             format!("Workdir: {}", options.workdir.display()),
             format!("State file: {}", options.state_path.display()),
         ];
-        state.set_state(&step.id, StepState::lifecycle_error(lifecycle));
+        let error_state = match failing_exit_code {
+            Some(code) if code != 0 => StepState::from_stage_result(lifecycle, code),
+            _ => StepState::lifecycle_error(lifecycle),
+        };
+        state.set_state(&step.id, error_state);
         if let Err(save_err) = state.save(&options.state_path) {
             logger.log_error(&format!("Failed to save error state: {save_err}"));
         }
@@ -186,6 +283,43 @@ pub fn run_lifecycle(
     Ok(true)
 }
 
+/// Drive every pending step through [`next_action`]/[`run_lifecycle`] until the plan is exhausted,
+/// a gate pauses the run for human review, or a lifecycle fails outright, writing the accumulated
+/// [`RunReport`] to `options.report_path` once the run stops for any reason (request chunk2-4).
+///
+/// # Errors
+///
+/// Returns an error if a lifecycle step fails outright; a paused step or an exhausted step list
+/// both end the run successfully.
+pub fn run_to_completion(
+    config: &Config,
+    plan: &Plan,
+    steps: &StepsFile,
+    state: &mut StateFile,
+    options: &RunOptions,
+    logger: &Logger,
+) -> Result<()> {
+    let mut report = RunReport::new();
+
+    loop {
+        let Some(next) = next_action(steps, state, None)? else {
+            break;
+        };
+        match run_lifecycle(config, plan, state, options, logger, next.step, next.lifecycle, &mut report) {
+            Ok(true) => continue,
+            Ok(false) => break,
+            Err(err) => {
+                if let Err(write_err) = report.write_to(&options.report_path) {
+                    logger.log_error(&format!("Failed to write run report: {write_err}"));
+                }
+                return Err(err);
+            }
+        }
+    }
+
+    report.write_to(&options.report_path)
+}
+
 fn lifecycle_mapping(lifecycle: u8) -> Result<(StepState, StepState, &'static str)> {
     let mapping = match lifecycle {
         1 => (StepState::Planned, StepState::Implemented, "implement"),
@@ -473,7 +607,158 @@ fn truncate_output(output: &str, max_chars: usize) -> String {
     output[start..].trim().to_string()
 }
 
-fn run_gates(config: &Config, options: &RunOptions, logger: &Logger) -> Result<()> {
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const WATCH_QUIET_PERIOD: Duration = Duration::from_millis(200);
+
+/// Watch `options.workdir` for file changes and re-run gates after a quiet period, scoping each
+/// run to skip gates that nothing relevant changed for (e.g. `test` when only non-Rust files
+/// moved). Runs until interrupted; gate failures are logged rather than returned so the watch
+/// loop keeps going.
+///
+/// # Errors
+///
+/// Returns an error if the workdir cannot be scanned.
+pub fn watch(config: &Config, options: &RunOptions, logger: &Logger) -> Result<()> {
+    logger.log_step(&format!("Watching {} for changes", options.workdir.display()));
+    let mut snapshot = snapshot_tree(&options.workdir)?;
+
+    loop {
+        let changed = wait_for_quiet_change(&options.workdir, &mut snapshot)?;
+        logger.log_substep(&format!("Detected {} changed file(s): {}", changed.len(), changed.join(", ")));
+
+        let touches_rust_source = changed.iter().any(|path| path.ends_with(".rs"));
+        let touches_lockfile = changed.iter().any(|path| path.ends_with("Cargo.lock"));
+
+        for gate in scoped_gates(config, &options.workdir, logger) {
+            let name = gate.name.clone().unwrap_or_else(|| gate.command.clone());
+            if !gate_is_affected(&gate, touches_rust_source, touches_lockfile) {
+                logger.log_substep(&format!("Skipping gate '{name}' (unaffected by this change)"));
+                continue;
+            }
+            let (command, args) = gate.resolve();
+            logger.log_step(&format!("Re-running gate: {name}"));
+            if let Err(err) = run_command_with_fallback(
+                std::slice::from_ref(&command.to_string()),
+                args,
+                Some(&options.workdir),
+                logger,
+                &format!("gate: {name}"),
+            ) {
+                logger.log_error(&format!("Gate '{name}' failed: {err}"));
+            }
+        }
+    }
+}
+
+fn scoped_gates(config: &Config, workdir: &Path, logger: &Logger) -> Vec<GateCommand> {
+    if config.gates.is_empty() {
+        default_gates(workdir, logger)
+    } else {
+        config.gates.clone()
+    }
+}
+
+/// Whether `gate` needs to re-run given what changed: everything re-runs except `test`, which is
+/// skipped unless a `.rs` file or `Cargo.lock` (dependency graph) moved.
+fn gate_is_affected(gate: &GateCommand, touches_rust_source: bool, touches_lockfile: bool) -> bool {
+    let name = gate.name.as_deref().unwrap_or(gate.command.as_str());
+    if name == "test" {
+        return touches_rust_source || touches_lockfile;
+    }
+    true
+}
+
+type FileSnapshot = HashMap<PathBuf, SystemTime>;
+
+/// Record the modification time of every tracked file under `root`, skipping `target/` and
+/// `.git/` so build artifacts and VCS bookkeeping don't trigger spurious reruns.
+fn snapshot_tree(root: &Path) -> Result<FileSnapshot> {
+    let mut snapshot = HashMap::new();
+    collect_mtimes(root, &mut snapshot)?;
+    Ok(snapshot)
+}
+
+fn collect_mtimes(dir: &Path, snapshot: &mut FileSnapshot) -> Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err).with_context(|| format!("failed to read '{}'", dir.display())),
+    };
+    for entry in entries {
+        let entry = entry.with_context(|| format!("failed to read entry in '{}'", dir.display()))?;
+        let path = entry.path();
+        if is_watch_ignored(&path) {
+            continue;
+        }
+        let metadata = entry
+            .metadata()
+            .with_context(|| format!("failed to stat '{}'", path.display()))?;
+        if metadata.is_dir() {
+            collect_mtimes(&path, snapshot)?;
+        } else {
+            snapshot.insert(path, metadata.modified().unwrap_or(UNIX_EPOCH));
+        }
+    }
+    Ok(())
+}
+
+fn is_watch_ignored(path: &Path) -> bool {
+    path.components()
+        .any(|component| matches!(component.as_os_str().to_str(), Some("target" | ".git")))
+}
+
+/// Poll `root` until file modification times stop changing for [`WATCH_QUIET_PERIOD`], debouncing
+/// rapid successive saves into a single batch. Returns the changed paths (relative to `root` when
+/// possible) and leaves `snapshot` updated to the latest state.
+fn wait_for_quiet_change(root: &Path, snapshot: &mut FileSnapshot) -> Result<Vec<String>> {
+    let mut last_change_at: Option<Instant> = None;
+    let mut changed_paths: BTreeSet<PathBuf> = BTreeSet::new();
+
+    loop {
+        std::thread::sleep(WATCH_POLL_INTERVAL);
+        let current = snapshot_tree(root)?;
+        let diff = diff_snapshots(snapshot, &current);
+        *snapshot = current;
+
+        if !diff.is_empty() {
+            changed_paths.extend(diff);
+            last_change_at = Some(Instant::now());
+            continue;
+        }
+
+        let Some(changed_at) = last_change_at else {
+            continue;
+        };
+        if changed_at.elapsed() >= WATCH_QUIET_PERIOD {
+            return Ok(changed_paths
+                .into_iter()
+                .map(|path| path.strip_prefix(root).unwrap_or(&path).display().to_string())
+                .collect());
+        }
+    }
+}
+
+fn diff_snapshots(before: &FileSnapshot, after: &FileSnapshot) -> BTreeSet<PathBuf> {
+    let mut changed = BTreeSet::new();
+    for (path, modified) in after {
+        if before.get(path) != Some(modified) {
+            changed.insert(path.clone());
+        }
+    }
+    for path in before.keys() {
+        if !after.contains_key(path) {
+            changed.insert(path.clone());
+        }
+    }
+    changed
+}
+
+fn run_gates(
+    config: &Config,
+    options: &RunOptions,
+    logger: &Logger,
+    gate_records: &mut Vec<report::GateRecord>,
+) -> Result<()> {
     logger.log_step("Gates: lint/build/test");
     let gates = if config.gates.is_empty() {
         default_gates(&options.workdir, logger)
@@ -481,18 +766,426 @@ fn run_gates(config: &Config, options: &RunOptions, logger: &Logger) -> Result<(
         config.gates.clone()
     };
 
+    if config.gates_parallel {
+        run_gates_parallel(&gates, config, options, logger, gate_records)
+    } else {
+        run_gates_sequential(&gates, options, logger, gate_records)
+    }
+}
+
+/// Run every gate strictly in declaration order, aborting on the first failure and pausing (via
+/// [`UnstableGate`]) on the first gate whose exit code is configured as unstable.
+fn run_gates_sequential(
+    gates: &[GateCommand],
+    options: &RunOptions,
+    logger: &Logger,
+    gate_records: &mut Vec<report::GateRecord>,
+) -> Result<()> {
     for gate in gates {
-        let name = gate.name.unwrap_or_else(|| gate.command.clone());
+        let name = gate.name.clone().unwrap_or_else(|| gate.command.clone());
         logger.log_substep(&format!("Running gate: {name}"));
-        run_command_with_fallback(
-            std::slice::from_ref(&gate.command),
-            &gate.args,
-            Some(&options.workdir),
-            logger,
-            &format!("gate: {name}"),
-        )?;
+        let (success, record) = run_gate_capturing_with_retry(gate, &options.workdir, logger);
+        let unstable = record.unstable;
+        gate_records.push(record);
+        if unstable {
+            return Err(UnstableGate(name).into());
+        }
+        if !success {
+            return Err(anyhow!("gate '{name}' failed"));
+        }
+    }
+    Ok(())
+}
+
+/// Run gates concurrently where possible, bounded by [`Config::gate_parallelism`]. Consecutive
+/// non-`sequential` gates form a batch that runs at once; a `sequential` gate runs alone before
+/// the next batch starts. Every gate still runs (no abort-on-first-failure), and all failures are
+/// reported together afterward in declaration order.
+fn run_gates_parallel(
+    gates: &[GateCommand],
+    config: &Config,
+    options: &RunOptions,
+    logger: &Logger,
+    gate_records: &mut Vec<report::GateRecord>,
+) -> Result<()> {
+    let max_parallel = config.gate_parallelism().max(1);
+    let multi = MultiProgress::new();
+    let mut results: Vec<Option<(bool, report::GateRecord)>> = (0..gates.len()).map(|_| None).collect();
+
+    let mut index = 0;
+    while index < gates.len() {
+        if gates[index].sequential {
+            logger.log_substep(&format!(
+                "Running gate '{}' sequentially",
+                gates[index].name.clone().unwrap_or_else(|| gates[index].command.clone())
+            ));
+            results[index] = Some(run_gate_capturing_with_retry(&gates[index], &options.workdir, logger));
+            index += 1;
+            continue;
+        }
+
+        let mut batch_end = index;
+        while batch_end < gates.len() && !gates[batch_end].sequential && batch_end - index < max_parallel {
+            batch_end += 1;
+        }
+        logger.log_substep(&format!("Running {} gate(s) in parallel", batch_end - index));
+        for (offset, result) in run_gate_batch(&gates[index..batch_end], &options.workdir, logger, &multi).into_iter().enumerate() {
+            results[index + offset] = Some(result);
+        }
+        index = batch_end;
+    }
+
+    let mut failures = Vec::new();
+    let mut unstable_name = None;
+    for result in results {
+        let (success, record) = result.expect("every gate index is populated by the loop above");
+        if record.unstable {
+            unstable_name.get_or_insert_with(|| record.name.clone());
+        } else if !success {
+            failures.push(record.name.clone());
+        }
+        gate_records.push(record);
+    }
+
+    if let Some(name) = unstable_name {
+        return Err(UnstableGate(name).into());
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("gate(s) failed: {}", failures.join(", ")))
+    }
+}
+
+/// Run a batch of independent gates concurrently, showing one progress line per gate via `multi`.
+fn run_gate_batch(
+    gates: &[GateCommand],
+    workdir: &Path,
+    logger: &Logger,
+    multi: &MultiProgress,
+) -> Vec<(bool, report::GateRecord)> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = gates
+            .iter()
+            .map(|gate| {
+                let name = gate.name.clone().unwrap_or_else(|| gate.command.clone());
+                let bar = multi.add(ProgressBar::new_spinner());
+                bar.set_style(
+                    ProgressStyle::with_template("{spinner} {msg}")
+                        .unwrap_or_else(|_| ProgressStyle::default_spinner())
+                        .tick_strings(&["|", "/", "-", "\\"]),
+                );
+                bar.set_message(format!("Running gate: {name}"));
+                bar.enable_steady_tick(Duration::from_millis(120));
+                scope.spawn(move || {
+                    let result = run_gate_capturing_with_retry(gate, workdir, logger);
+                    bar.finish_and_clear();
+                    result
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .zip(gates.iter())
+            .map(|(handle, gate)| {
+                handle.join().unwrap_or_else(|_| {
+                    let name = gate.name.clone().unwrap_or_else(|| gate.command.clone());
+                    (
+                        false,
+                        report::GateRecord {
+                            name,
+                            command_line: gate.command.clone(),
+                            success: false,
+                            duration_ms: 0,
+                            stdout_tail: Vec::new(),
+                            stderr_tail: vec!["gate thread panicked".to_string()],
+                            diagnostics: Vec::new(),
+                            exit_code: None,
+                            unstable: false,
+                        },
+                    )
+                })
+            })
+            .collect()
+    })
+}
+
+/// Run a gate, retrying once via auto-repair of machine-applicable compiler suggestions on failure
+/// (request chunk2-1). Shared by both the sequential and parallel execution paths.
+fn run_gate_capturing_with_retry(gate: &GateCommand, workdir: &Path, logger: &Logger) -> (bool, report::GateRecord) {
+    let name = gate.name.clone().unwrap_or_else(|| gate.command.clone());
+    let (mut success, mut record) = run_gate_capturing(gate, workdir, logger);
+
+    if !success && is_rustfix_candidate(gate) {
+        logger.log_substep(&format!("Gate '{name}' failed; attempting auto-repair of machine-applicable suggestions"));
+        if run_auto_repair(gate, workdir, logger).is_ok() {
+            logger.log_substep(&format!("Auto-repair applied; re-running gate: {name}"));
+            let retried = run_gate_capturing(gate, workdir, logger);
+            success = retried.0;
+            record = retried.1;
+        }
+    }
+
+    (success, record)
+}
+
+/// How a gate's raw exit code should be treated: a clean pass continues normally; an
+/// `unstable_exit_code` match pauses the lifecycle for human review without being recorded as a
+/// hard failure; anything else aborts the stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StageOutcome {
+    Pass,
+    Unstable,
+    Failure,
+}
+
+impl StageOutcome {
+    fn from_exit_code(exit_code: i32, unstable_exit_code: Option<i32>) -> Self {
+        match exit_code {
+            0 => Self::Pass,
+            code if unstable_exit_code == Some(code) => Self::Unstable,
+            _ => Self::Failure,
+        }
+    }
+}
+
+/// Raised instead of a plain failure when a gate's exit code matched its configured
+/// `unstable_exit_code`, so `run_lifecycle` can tell this apart from a hard failure and pause the
+/// step (`StepState::Paused`) rather than recording a `LifecycleError`.
+#[derive(Debug)]
+struct UnstableGate(String);
+
+impl std::fmt::Display for UnstableGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "gate '{}' is unstable; pausing for human review", self.0)
+    }
+}
+
+impl std::error::Error for UnstableGate {}
+
+/// Run a single gate to completion, capturing stdout/stderr and timing into a
+/// [`report::GateRecord`] regardless of outcome. Unlike `run_command_with_fallback`, this always
+/// runs the platform-resolved command directly (gates never have multiple candidate programs).
+fn run_gate_capturing(gate: &GateCommand, workdir: &Path, logger: &Logger) -> (bool, report::GateRecord) {
+    let (command, args) = gate.resolve();
+    let name = gate.name.clone().unwrap_or_else(|| command.to_string());
+    let command_line = format!("{command} {}", args.join(" "));
+    logger.log_substep(&format!("Executing gate: {command_line}"));
+
+    let started = Instant::now();
+    let output = Command::new(command).args(args).current_dir(workdir).output();
+    let duration_ms = started.elapsed().as_millis();
+
+    match output {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            for line in stdout.lines() {
+                logger.log_output(line);
+            }
+            for line in stderr.lines() {
+                logger.log_output(line);
+            }
+            let diagnostics = if args.iter().any(|arg| arg == "--message-format=json") {
+                report::parse_cargo_diagnostics(&stdout)
+            } else {
+                Vec::new()
+            };
+            let exit_code = output.status.code();
+            let outcome = StageOutcome::from_exit_code(exit_code.unwrap_or(-1), gate.unstable_exit_code);
+            if outcome == StageOutcome::Unstable {
+                logger.log_substep(&format!(
+                    "Gate '{name}' exited {} (configured as unstable); pausing rather than failing the run",
+                    exit_code.unwrap_or(-1)
+                ));
+            }
+            let success = outcome != StageOutcome::Failure;
+            (
+                success,
+                report::GateRecord {
+                    name,
+                    command_line,
+                    success,
+                    duration_ms,
+                    stdout_tail: tail_lines(&stdout, 20),
+                    stderr_tail: tail_lines(&stderr, 20),
+                    diagnostics,
+                    exit_code,
+                    unstable: outcome == StageOutcome::Unstable,
+                },
+            )
+        }
+        Err(err) => {
+            logger.log_error(&format!("failed to run gate '{name}': {err}"));
+            (
+                false,
+                report::GateRecord {
+                    name,
+                    command_line,
+                    success: false,
+                    duration_ms,
+                    stdout_tail: Vec::new(),
+                    stderr_tail: vec![err.to_string()],
+                    diagnostics: Vec::new(),
+                    exit_code: None,
+                    unstable: false,
+                },
+            )
+        }
     }
+}
+
+fn tail_lines(text: &str, limit: usize) -> Vec<String> {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    if lines.len() > limit {
+        let drop = lines.len() - limit;
+        lines.drain(0..drop);
+    }
+    lines
+}
 
+/// Whether `gate` is a `cargo build`/`check`/`clippy` invocation that emits `rustc`-style
+/// `--message-format=json` diagnostics [`run_auto_repair`] can parse for machine-applicable
+/// suggestions (never `test`, which doesn't emit them and where a passing build doesn't imply
+/// passing tests).
+fn is_rustfix_candidate(gate: &GateCommand) -> bool {
+    gate.command == "cargo"
+        && gate
+            .args
+            .iter()
+            .any(|arg| matches!(arg.as_str(), "build" | "check" | "clippy"))
+}
+
+/// One compiler suggestion safe to apply without review: a byte range in `file` to replace with
+/// `replacement`, lifted from a `--message-format=json` diagnostic whose `suggestion_applicability`
+/// was `"MachineApplicable"`.
+struct MachineApplicableFix {
+    file: String,
+    byte_start: usize,
+    byte_end: usize,
+    replacement: String,
+}
+
+/// Parse `cargo ... --message-format=json` stdout into the machine-applicable suggestions within
+/// it, ignoring every other diagnostic (uncertain/maybe-incorrect suggestions, or spans with no
+/// suggestion at all).
+fn parse_machine_applicable_fixes(stdout: &str) -> Vec<MachineApplicableFix> {
+    let mut fixes = Vec::new();
+    for line in stdout.lines() {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if value.get("reason").and_then(serde_json::Value::as_str) != Some("compiler-message") {
+            continue;
+        }
+        let Some(spans) = value.pointer("/message/spans").and_then(serde_json::Value::as_array) else {
+            continue;
+        };
+        for span in spans {
+            if span.get("suggestion_applicability").and_then(serde_json::Value::as_str) != Some("MachineApplicable") {
+                continue;
+            }
+            let file = span.get("file_name").and_then(serde_json::Value::as_str);
+            let byte_start = span.get("byte_start").and_then(serde_json::Value::as_u64);
+            let byte_end = span.get("byte_end").and_then(serde_json::Value::as_u64);
+            let replacement = span.get("suggested_replacement").and_then(serde_json::Value::as_str);
+            if let (Some(file), Some(byte_start), Some(byte_end), Some(replacement)) =
+                (file, byte_start, byte_end, replacement)
+            {
+                fixes.push(MachineApplicableFix {
+                    file: file.to_string(),
+                    byte_start: byte_start as usize,
+                    byte_end: byte_end as usize,
+                    replacement: replacement.to_string(),
+                });
+            }
+        }
+    }
+    fixes
+}
+
+/// Insert `--message-format=json` into a gate's args, before the `--` separator (if the gate has
+/// one, e.g. `clippy -- -D warnings`) so it lands as a `cargo` flag rather than a tool flag.
+fn args_with_json_format(args: &[String]) -> Vec<String> {
+    let mut result = Vec::with_capacity(args.len() + 1);
+    match args.iter().position(|arg| arg == "--") {
+        Some(separator) => {
+            result.extend_from_slice(&args[..separator]);
+            result.push("--message-format=json".to_string());
+            result.extend_from_slice(&args[separator..]);
+        }
+        None => {
+            result.extend_from_slice(args);
+            result.push("--message-format=json".to_string());
+        }
+    }
+    result
+}
+
+/// Rustfix-style auto-repair (request chunk2-1): re-run `gate`'s command with
+/// `--message-format=json`, keep only `MachineApplicable` suggestions, and splice each one's
+/// replacement into its file by descending byte offset (so earlier splices never invalidate later
+/// offsets in the same file), skipping any suggestion whose span overlaps one already applied.
+/// Leaves the caller to re-run the original gate afterward.
+///
+/// # Errors
+///
+/// Returns an error if the diagnostic gate can't be spawned, no machine-applicable suggestions were
+/// found, or a patched file can't be read or written.
+fn run_auto_repair(gate: &GateCommand, workdir: &Path, logger: &Logger) -> Result<()> {
+    let (command, args) = gate.resolve();
+    let json_args = args_with_json_format(args);
+    let command_line = format!("{command} {}", json_args.join(" "));
+    logger.log_substep(&format!("Executing auto-repair diagnostics: {command_line}"));
+
+    let output = Command::new(command)
+        .args(&json_args)
+        .current_dir(workdir)
+        .output()
+        .with_context(|| format!("failed to run '{command_line}'"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fixes = parse_machine_applicable_fixes(&stdout);
+    if fixes.is_empty() {
+        bail!("no machine-applicable suggestions found in '{command_line}' output");
+    }
+
+    let mut by_file: HashMap<String, Vec<MachineApplicableFix>> = HashMap::new();
+    for fix in fixes {
+        by_file.entry(fix.file.clone()).or_default().push(fix);
+    }
+
+    let mut applied = 0usize;
+    for (file, mut file_fixes) in by_file {
+        file_fixes.sort_by(|a, b| b.byte_start.cmp(&a.byte_start));
+        let path = workdir.join(&file);
+        let mut source = fs::read_to_string(&path).with_context(|| format!("failed to read '{}'", path.display()))?;
+
+        // The earliest byte offset still untouched by an already-applied (later in file) fix; a
+        // span ending past it would overlap that fix, so it's skipped rather than corrupting it.
+        let mut earliest_untouched = source.len();
+        for fix in &file_fixes {
+            if fix.byte_start > source.len() || fix.byte_end > source.len() || fix.byte_start > fix.byte_end {
+                continue;
+            }
+            if fix.byte_end > earliest_untouched {
+                continue;
+            }
+            source.replace_range(fix.byte_start..fix.byte_end, &fix.replacement);
+            logger.log_substep(&format!(
+                "Auto-repair: applied suggestion in '{file}' [{}, {})",
+                fix.byte_start, fix.byte_end
+            ));
+            earliest_untouched = fix.byte_start;
+            applied += 1;
+        }
+        fs::write(&path, source).with_context(|| format!("failed to write '{}'", path.display()))?;
+    }
+
+    if applied == 0 {
+        bail!("every machine-applicable suggestion in '{command_line}' output overlapped another");
+    }
+    logger.log_substep(&format!("Auto-repair applied {applied} fix(es)"));
     Ok(())
 }
 
@@ -507,6 +1200,9 @@ fn default_gates(workdir: &Path, logger: &Logger) -> Vec<GateCommand> {
             name: Some("fmt-check".to_string()),
             command: "cargo".to_string(),
             args: vec!["fmt".to_string(), "--check".to_string()],
+            windows: None,
+            sequential: false,
+            unstable_exit_code: None,
         },
         GateCommand {
             name: Some("clippy".to_string()),
@@ -517,20 +1213,60 @@ fn default_gates(workdir: &Path, logger: &Logger) -> Vec<GateCommand> {
                 "-D".to_string(),
                 "warnings".to_string(),
             ],
+            windows: None,
+            sequential: false,
+            unstable_exit_code: None,
         },
         GateCommand {
             name: Some("build".to_string()),
             command: "cargo".to_string(),
             args: vec!["build".to_string()],
+            windows: None,
+            sequential: true,
+            unstable_exit_code: None,
         },
         GateCommand {
             name: Some("test".to_string()),
             command: "cargo".to_string(),
             args: vec!["test".to_string()],
+            windows: None,
+            sequential: true,
+            unstable_exit_code: None,
         },
     ]
 }
 
+/// Apply a unified-diff patch file (such as one produced by [`write_git_diff`], or a patch an
+/// agent returned instead of editing files in place) to `workdir`. Hunks that match are applied
+/// even if others don't; any rejected hunks are returned as an error summarizing which ones and
+/// why, so the caller can decide whether a partial apply is acceptable.
+///
+/// # Errors
+///
+/// Returns an error if the patch file can't be read, a target file can't be read or written, or
+/// one or more hunks couldn't be matched against the current file content.
+pub fn apply_patch_file(workdir: &Path, patch_path: &Path, logger: &Logger) -> Result<()> {
+    let patch_text = std::fs::read_to_string(patch_path)
+        .with_context(|| format!("failed to read patch file: {}", patch_path.display()))?;
+    let result = patch::apply_patch(workdir, &patch_text, logger)?;
+    if result.all_applied() {
+        logger.log_substep(&format!("Applied {} hunk(s) from {}", result.applied_hunks, patch_path.display()));
+        return Ok(());
+    }
+    let failures = result
+        .failed_hunks
+        .iter()
+        .map(|failure| format!("{} {} ({})", failure.file, failure.header, failure.reason))
+        .collect::<Vec<_>>()
+        .join("; ");
+    Err(anyhow!(
+        "{} of {} hunk(s) in {} could not be applied: {failures}",
+        result.failed_hunks.len(),
+        result.failed_hunks.len() + result.applied_hunks,
+        patch_path.display()
+    ))
+}
+
 fn write_git_diff(workdir: &Path, logger: &Logger) -> Result<PathBuf> {
     let diff_output = Command::new("git")
         .args(["diff"])
@@ -729,7 +1465,7 @@ fn run_git_commit(
     options: &RunOptions,
     logger: &Logger,
     lifecycle: u8,
-) -> Result<()> {
+) -> Result<Option<CommitInfo>> {
     let status_output = Command::new("git")
         .args(["status", "--porcelain"])
         .current_dir(&options.workdir)
@@ -738,7 +1474,7 @@ fn run_git_commit(
     let status_text = String::from_utf8_lossy(&status_output.stdout);
     if status_text.trim().is_empty() {
         logger.log_substep("No changes to commit; skipping git commit");
-        return Ok(());
+        return Ok(None);
     }
     let message = format!(
         "stage implemented-finalized: step {} - {}",
@@ -751,11 +1487,254 @@ fn run_git_commit(
         logger,
         "git add",
     )?;
+
+    let mut commit_args = vec!["commit".to_string(), "-m".to_string(), message.clone()];
+    if options.hook_policy == HookPolicy::Skip {
+        commit_args.push("--no-verify".to_string());
+    }
+
+    match options.hook_policy {
+        HookPolicy::Run | HookPolicy::Skip => run_command_with_fallback(
+            &["git".to_string()],
+            &commit_args,
+            Some(&options.workdir),
+            logger,
+            &format!("git commit (lifecycle {lifecycle})"),
+        ),
+        HookPolicy::RunAndCapture => {
+            run_git_commit_with_hook_capture(&commit_args, &message, options, logger, lifecycle)
+        }
+    }?;
+
+    Ok(capture_commit_info(&options.workdir, lifecycle))
+}
+
+/// Run `git commit` with hooks enabled, retrying once with `--no-verify` if a hook rejects it.
+fn run_git_commit_with_hook_capture(
+    commit_args: &[String],
+    message: &str,
+    options: &RunOptions,
+    logger: &Logger,
+    lifecycle: u8,
+) -> Result<()> {
+    let first_attempt = run_command_with_fallback(
+        &["git".to_string()],
+        commit_args,
+        Some(&options.workdir),
+        logger,
+        &format!("git commit (lifecycle {lifecycle}, hooks run)"),
+    );
+    if first_attempt.is_ok() {
+        return first_attempt;
+    }
+
+    logger.log_error(&format!(
+        "Pre-commit hook rejected lifecycle {lifecycle} commit; retrying with --no-verify"
+    ));
     run_command_with_fallback(
         &["git".to_string()],
-        &["commit".to_string(), "-m".to_string(), message],
+        &[
+            "commit".to_string(),
+            "-m".to_string(),
+            message.to_string(),
+            "--no-verify".to_string(),
+        ],
         Some(&options.workdir),
         logger,
-        &format!("git commit (lifecycle {lifecycle})"),
+        &format!("git commit (lifecycle {lifecycle}, hooks bypassed)"),
     )
 }
+
+/// Resolve a usable working directory for the lifecycle: `workdir` if given and non-empty,
+/// otherwise `$HOME`/`%USERPROFILE%`, otherwise a passwd-entry probe (mirroring the POSIX fallback
+/// historically used by `std::env::home_dir`), otherwise the current directory. The result is
+/// canonicalized and confirmed to be inside a git work tree.
+///
+/// # Errors
+///
+/// Returns an error if no usable directory can be found, canonicalization fails, or the resolved
+/// path is not inside a git work tree.
+pub fn resolve_workdir(workdir: Option<&Path>) -> Result<PathBuf> {
+    let candidate = match workdir {
+        Some(path) if !path.as_os_str().is_empty() => path.to_path_buf(),
+        _ => default_workdir()?,
+    };
+
+    let canonical = candidate
+        .canonicalize()
+        .with_context(|| format!("failed to resolve workdir '{}'", candidate.display()))?;
+
+    let is_work_tree = Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(&canonical)
+        .output()
+        .map(|output| output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true")
+        .unwrap_or(false);
+    if !is_work_tree {
+        bail!(
+            "resolved workdir '{}' is not inside a git work tree",
+            canonical.display()
+        );
+    }
+
+    Ok(canonical)
+}
+
+fn default_workdir() -> Result<PathBuf> {
+    if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+        let path = PathBuf::from(home);
+        if !path.as_os_str().is_empty() {
+            return Ok(path);
+        }
+    }
+    if let Some(home) = probe_passwd_home_dir() {
+        return Ok(home);
+    }
+    std::env::current_dir()
+        .context("no $HOME/%USERPROFILE%, passwd entry, or readable current directory to fall back to")
+}
+
+/// Best-effort fallback when `$HOME`/`%USERPROFILE%` isn't set: ask `getent passwd` for the
+/// invoking user's home directory, the same passwd-entry fallback `std::env::home_dir` historically
+/// used on POSIX before it was deprecated for being unreliable under cross-compilation.
+fn probe_passwd_home_dir() -> Option<PathBuf> {
+    let username = std::env::var("USER").or_else(|_| std::env::var("LOGNAME")).ok()?;
+    let output = Command::new("getent").args(["passwd", &username]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    let home = text.trim().split(':').nth(5)?;
+    if home.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(home))
+    }
+}
+
+const LIFECYCLE_HOOK_BEGIN: &str = "# >>> prime-agent lifecycle hook >>>";
+const LIFECYCLE_HOOK_END: &str = "# <<< prime-agent lifecycle hook <<<";
+
+/// Interactively install a `pre-commit` (and, if `include_post_commit`, `post-commit`) hook under
+/// `<workdir>/.git/hooks` that shells back into `prime-agent run`, so the agent's lifecycle keeps
+/// advancing on every developer commit rather than only when invoked directly. Unlike
+/// [`crate::hooks::install`], which refuses to touch an unrelated hook unless forced, this asks
+/// `[y/N]` before installing, and again before overwriting a hook that isn't already ours.
+///
+/// # Errors
+///
+/// Returns an error if `.git/hooks` is missing, or an existing hook can't be read or written.
+pub fn install_lifecycle_hook(workdir: &Path, include_post_commit: bool) -> Result<()> {
+    if !prompt_yes_no("Install the prime-agent lifecycle git hook(s)?")? {
+        return Ok(());
+    }
+    install_one_hook(workdir, "pre-commit")?;
+    if include_post_commit {
+        install_one_hook(workdir, "post-commit")?;
+    }
+    Ok(())
+}
+
+fn install_one_hook(workdir: &Path, hook_name: &str) -> Result<()> {
+    let hooks_dir = workdir.join(".git").join("hooks");
+    if !hooks_dir.is_dir() {
+        bail!("'{}' is not a git hooks directory", hooks_dir.display());
+    }
+    let hook_path = hooks_dir.join(hook_name);
+
+    if hook_path.exists() {
+        let existing = fs::read_to_string(&hook_path)
+            .with_context(|| format!("failed to read existing hook '{}'", hook_path.display()))?;
+        if existing.contains(LIFECYCLE_HOOK_BEGIN) {
+            return Ok(());
+        }
+        if !prompt_yes_no(&format!(
+            "'{}' already has a {hook_name} hook; overwrite it?",
+            hook_path.display()
+        ))? {
+            return Ok(());
+        }
+    }
+
+    let script = format!("#!/bin/sh\n{}\n", lifecycle_hook_block(hook_name));
+    fs::write(&hook_path, script).with_context(|| format!("failed to write hook '{}'", hook_path.display()))?;
+    let mut permissions = fs::metadata(&hook_path)
+        .with_context(|| format!("failed to stat hook '{}'", hook_path.display()))?
+        .permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(&hook_path, permissions)
+        .with_context(|| format!("failed to make hook executable: '{}'", hook_path.display()))
+}
+
+fn lifecycle_hook_block(hook_name: &str) -> String {
+    format!("{LIFECYCLE_HOOK_BEGIN}\nprime-agent run --{hook_name} || true\n{LIFECYCLE_HOOK_END}")
+}
+
+/// Prompt `[y/N]`, looping on unrecognized input; empty input, EOF, or `n`/`no` all decline.
+fn prompt_yes_no(question: &str) -> Result<bool> {
+    loop {
+        print!("{question} [y/N] ");
+        std::io::stdout().flush().ok();
+        let mut input = String::new();
+        let read = std::io::stdin().read_line(&mut input)?;
+        if read == 0 {
+            return Ok(false);
+        }
+        match input.trim().to_ascii_lowercase().as_str() {
+            "" | "n" | "no" => return Ok(false),
+            "y" | "yes" => return Ok(true),
+            _ => println!("Please answer 'y' or 'n'."),
+        }
+    }
+}
+
+/// A lifecycle stage's resulting commit, captured best-effort after `git commit` succeeds.
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub date: String,
+    pub lifecycle: u8,
+}
+
+/// Probe `workdir` for the commit `git commit` just produced, the way a `build.rs` probes repo
+/// metadata: shell out to `git`, and treat any failure (git missing, not a repo, detached weirdness)
+/// as "no info available" rather than an error, so automation never blocks on this.
+#[must_use]
+pub fn capture_commit_info(workdir: &Path, lifecycle: u8) -> Option<CommitInfo> {
+    let hash = run_git_probe(workdir, &["rev-parse", "HEAD"])?;
+    let date = run_git_probe(workdir, &["log", "-1", "--format=%cI"])?;
+    Some(CommitInfo { hash, date, lifecycle })
+}
+
+fn run_git_probe(workdir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).current_dir(workdir).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_options_new_resolves_workdir() {
+        let repo_root = std::env::current_dir().expect("current dir");
+        let options = RunOptions::new(
+            PathBuf::from("plan.json"),
+            PathBuf::from("state.json"),
+            Some(&repo_root),
+            HookPolicy::Run,
+            PathBuf::from("run-report.json"),
+        )
+        .expect("resolve workdir inside git work tree");
+        assert_eq!(options.workdir, repo_root.canonicalize().expect("canonicalize"));
+    }
+}
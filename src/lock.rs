@@ -0,0 +1,197 @@
+use crate::registry;
+use crate::skills_store::SkillsStore;
+use crate::sync::{self, SyncStatus};
+use anyhow::{Context, Result};
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Infallible, Serialize as RkyvSerialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// On-disk format version for `.prime-agent.lock`. Bump whenever [`SkillLockEntry`]'s archived
+/// layout changes, so a lock written by an older build is rebuilt instead of misread.
+const LOCK_FORMAT_VERSION: u32 = 1;
+
+const LOCK_FILE_NAME: &str = ".prime-agent.lock";
+
+/// One skill's recorded state as of its last `set`/`sync`.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+pub struct SkillLockEntry {
+    /// Where this skill's content last came from: its `SKILL.md` path.
+    pub source_path: String,
+    /// SHA-256 of the skill's canonicalized content.
+    pub content_hash: String,
+    /// SHA-256 of the `AGENTS.md` section content as of the last sync, if it's ever been synced.
+    pub agents_hash: Option<String>,
+}
+
+/// The archived, on-disk shape of `.prime-agent.lock`: a version tag plus every skill's entry.
+/// `rkyv`'s `check_bytes` validation lets [`SkillLock::load`] reject a malformed buffer before
+/// deserializing it; a buffer that passes is then deserialized in full, every entry at once.
+#[derive(Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct LockFile {
+    version: u32,
+    entries: Vec<(String, SkillLockEntry)>,
+}
+
+/// A loaded `.prime-agent.lock`: per-skill content hashes so `local`/`sync` can tell what changed
+/// since the last recorded sync by comparing hashes. `AGENTS.md` is still parsed, and each
+/// locked skill's file is still read to hash it.
+pub struct SkillLock {
+    path: PathBuf,
+    entries: HashMap<String, SkillLockEntry>,
+    dirty: bool,
+}
+
+impl SkillLock {
+    /// Load `<skills_dir>/.prime-agent.lock`. A missing file, a corrupt one, or one written by an
+    /// incompatible [`LOCK_FORMAT_VERSION`] all start an empty lock rather than erroring --
+    /// `record`ing skills as they're touched rebuilds it from scratch.
+    #[must_use]
+    pub fn load(skills_dir: &Path) -> Self {
+        let path = skills_dir.join(LOCK_FILE_NAME);
+        let entries = Self::read(&path).unwrap_or_default();
+        Self { path, entries, dirty: false }
+    }
+
+    fn read(path: &Path) -> Option<HashMap<String, SkillLockEntry>> {
+        let bytes = fs::read(path).ok()?;
+        let archived = rkyv::check_archived_root::<LockFile>(&bytes).ok()?;
+        if archived.version != LOCK_FORMAT_VERSION {
+            return None;
+        }
+        let lock_file: LockFile = archived.deserialize(&mut Infallible).ok()?;
+        Some(lock_file.entries.into_iter().collect())
+    }
+
+    /// Rebuild the lock from the skills currently on disk, with no `agents_hash` recorded for any
+    /// of them (there's no history to recover it from) -- the migration path for a missing or
+    /// outdated lock file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a skill's content can't be read.
+    pub fn rebuild(skills_store: &SkillsStore) -> Result<Self> {
+        let mut lock = Self {
+            path: skills_store.root().join(LOCK_FILE_NAME),
+            entries: HashMap::new(),
+            dirty: true,
+        };
+        for name in skills_store.list_skill_names()? {
+            let content = skills_store.load_skill(&name)?;
+            let source_path = skills_store.root().join(&name).join("SKILL.md");
+            lock.record(&name, &source_path, &content, None);
+        }
+        Ok(lock)
+    }
+
+    /// The recorded entry for `name`, if the lock has one.
+    #[must_use]
+    pub fn entry(&self, name: &str) -> Option<&SkillLockEntry> {
+        self.entries.get(name)
+    }
+
+    /// Whether the lock has no entries at all -- true both for a brand-new skills dir and for a
+    /// missing/incompatible-version lock file, which `load` treats the same way.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record (or replace) `name`'s entry: the content hash is always recomputed from `content`;
+    /// `agents_content`, when given, updates the last-synced `AGENTS.md` section hash, otherwise
+    /// the previous `agents_hash` (if any) is preserved.
+    pub fn record(&mut self, name: &str, source_path: &Path, content: &str, agents_content: Option<&str>) {
+        let agents_hash = agents_content
+            .map(|content| registry::content_hash(&sync::normalize_content(content)))
+            .or_else(|| self.entries.get(name).and_then(|entry| entry.agents_hash.clone()));
+        self.entries.insert(
+            name.to_string(),
+            SkillLockEntry {
+                source_path: source_path.to_string_lossy().to_string(),
+                content_hash: registry::content_hash(&sync::normalize_content(content)),
+                agents_hash,
+            },
+        );
+        self.dirty = true;
+    }
+
+    /// Clear `name`'s recorded `agents_hash` without forgetting its content hash, e.g. after its
+    /// `AGENTS.md` section is removed but the skill file itself is kept.
+    pub fn clear_agents_hash(&mut self, name: &str) {
+        if let Some(entry) = self.entries.get_mut(name) {
+            if entry.agents_hash.take().is_some() {
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Drop `name`'s entry entirely, e.g. after `delete-globally` removes the skill file.
+    pub fn remove(&mut self, name: &str) {
+        if self.entries.remove(name).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// Write the lock back to disk if anything changed since it was loaded or rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock can't be serialized or written.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let lock_file = LockFile {
+            version: LOCK_FORMAT_VERSION,
+            entries: self.entries.iter().map(|(name, entry)| (name.clone(), entry.clone())).collect(),
+        };
+        let bytes = rkyv::to_bytes::<_, 256>(&lock_file).context("failed to serialize skill lock")?;
+        fs::write(&self.path, bytes.as_slice())
+            .with_context(|| format!("failed to write '{}'", self.path.display()))?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Sync status for every skill with a lock entry, by comparing the recorded `content_hash`
+    /// and `agents_hash` to the hashes of the skill's current content (read from disk) and of its
+    /// section in `agents_sections` (which the caller has already parsed out of `AGENTS.md`). A
+    /// skill whose on-disk content no longer matches its recorded hash is reported as `Local`, or
+    /// `Conflict` if its section changed as well; a skill with no section is reported as `Local`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a skill's content can't be read.
+    pub fn compute_status(
+        &self,
+        skills_store: &SkillsStore,
+        agents_sections: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, SyncStatus>> {
+        let mut statuses = BTreeMap::new();
+        for (name, entry) in &self.entries {
+            if !skills_store.skill_exists(name) {
+                continue;
+            }
+            let content = skills_store.load_skill(name)?;
+            let current_hash = registry::content_hash(&sync::normalize_content(&content));
+            let skill_changed = current_hash != entry.content_hash;
+
+            let status = match agents_sections.get(name) {
+                Some(section) => {
+                    let agents_hash = registry::content_hash(&sync::normalize_content(section));
+                    let agents_changed = entry.agents_hash.as_deref() != Some(agents_hash.as_str());
+                    match (skill_changed, agents_changed) {
+                        (false, false) => SyncStatus::InSync,
+                        (true, false) => SyncStatus::Local,
+                        (false, true) => SyncStatus::Remote,
+                        (true, true) => SyncStatus::Conflict,
+                    }
+                }
+                None => SyncStatus::Local,
+            };
+            statuses.insert(name.clone(), status);
+        }
+        Ok(statuses)
+    }
+}
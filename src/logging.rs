@@ -1,32 +1,128 @@
+use crate::config::Config;
 use anyhow::{Context, Result};
 use owo_colors::OwoColorize;
-use std::fs::{File, OpenOptions, create_dir_all};
+use serde::Serialize;
+use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const DEFAULT_LOG_DIR: &str = "/tmp/prime-agent";
 const DEFAULT_LOG_FILE: &str = "prime-agent.log";
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_KEEP_ROTATIONS: usize = 5;
+
+/// Severity of a log line, ordered from least to most severe for level-threshold comparisons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+/// Tunables for [`Logger::with_options`].
+#[derive(Debug, Clone)]
+pub struct LoggerOptions {
+    /// Minimum level printed to the console.
+    pub console_level: Level,
+    /// Minimum level written to the log file.
+    pub file_level: Level,
+    /// Emit each line as a JSON object instead of plain text.
+    pub json: bool,
+    /// Directory the log file (and its rotations) are written to.
+    pub log_dir: PathBuf,
+    /// Rotate the log file once it exceeds this many bytes.
+    pub max_bytes: u64,
+    /// Number of rotated files (`prime-agent.log.1`, `.2`, ...) to keep before dropping the oldest.
+    pub keep_rotations: usize,
+}
+
+impl Default for LoggerOptions {
+    fn default() -> Self {
+        Self {
+            console_level: Level::Info,
+            file_level: Level::Debug,
+            json: false,
+            log_dir: PathBuf::from(DEFAULT_LOG_DIR),
+            max_bytes: DEFAULT_MAX_BYTES,
+            keep_rotations: DEFAULT_KEEP_ROTATIONS,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    level: &'a str,
+    message: &'a str,
+    unix_ms: u128,
+}
 
 #[derive(Clone)]
 pub struct Logger {
-    verbose: bool,
+    options: LoggerOptions,
     file: Arc<Mutex<File>>,
     log_path: PathBuf,
 }
 
 impl Logger {
+    /// Construct a logger with the legacy two-mode behavior: `verbose` prints substeps and
+    /// command output to the console in addition to errors and step headers.
     pub fn new(verbose: bool) -> Result<Self> {
-        let log_dir = Path::new(DEFAULT_LOG_DIR);
+        let options = LoggerOptions {
+            console_level: if verbose { Level::Debug } else { Level::Info },
+            ..LoggerOptions::default()
+        };
+        Self::with_options(options)
+    }
+
+    /// Construct a logger from a loaded [`Config`]'s `log-dir`/`log-max-bytes`/
+    /// `log-keep-rotations`/`log-json` settings, falling back to [`LoggerOptions::default`] for
+    /// whichever are unset. `verbose` still controls the console level the way [`Logger::new`]
+    /// does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log directory or file cannot be created or opened.
+    pub fn from_config(config: &Config, verbose: bool) -> Result<Self> {
+        let defaults = LoggerOptions::default();
+        let options = LoggerOptions {
+            console_level: if verbose { Level::Debug } else { Level::Info },
+            json: config.log_json.unwrap_or(defaults.json),
+            log_dir: config.log_dir.clone().map(PathBuf::from).unwrap_or(defaults.log_dir),
+            max_bytes: config.log_max_bytes.unwrap_or(defaults.max_bytes),
+            keep_rotations: config.log_keep_rotations.unwrap_or(defaults.keep_rotations),
+            ..defaults
+        };
+        Self::with_options(options)
+    }
+
+    /// Construct a logger with full control over console/file levels, JSON output, and rotation.
+    pub fn with_options(options: LoggerOptions) -> Result<Self> {
+        let log_dir = options.log_dir.as_path();
         create_dir_all(log_dir).context("failed to create log directory")?;
         let log_path = log_dir.join(DEFAULT_LOG_FILE);
+        rotate_if_needed(&log_path, options.max_bytes, options.keep_rotations)?;
         let file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&log_path)
             .context("failed to open log file")?;
         Ok(Self {
-            verbose,
+            options,
             file: Arc::new(Mutex::new(file)),
             log_path,
         })
@@ -39,45 +135,139 @@ impl Logger {
 
     /// Log a lifecycle step header in green and append to file.
     pub fn log_step(&self, message: &str) {
-        println!("{}", message.green());
-        self.write_line(message);
+        self.emit(Level::Info, message, |line| println!("{}", line.green()));
     }
 
     /// Log a substep message in dark gray when verbose and append to file.
     pub fn log_substep(&self, message: &str) {
-        if self.verbose {
-            println!("{}", message.bright_black());
-        }
-        self.write_line(message);
+        self.emit(Level::Debug, message, |line| println!("{}", line.bright_black()));
     }
 
     /// Log command output lines to file and optionally to console.
     pub fn log_output(&self, line: &str) {
-        if self.verbose {
-            println!("{}", line.bright_black());
-        }
-        self.write_line(line);
+        self.emit(Level::Debug, line, |line| println!("{}", line.bright_black()));
     }
 
     /// Log errors to stderr and append to file.
     pub fn log_error(&self, message: &str) {
-        eprintln!("{message}");
-        self.write_line(message);
+        self.emit(Level::Error, message, |line| eprintln!("{line}"));
     }
 
     /// Log error details with verbose output enforced.
     pub fn log_error_verbose(&self, message: &str, details: &[String]) {
-        eprintln!("{message}");
-        self.write_line(message);
+        self.emit(Level::Error, message, |line| eprintln!("{line}"));
         for line in details {
-            eprintln!("{line}");
-            self.write_line(line);
+            self.emit(Level::Error, line, |line| eprintln!("{line}"));
+        }
+    }
+
+    fn emit(&self, level: Level, message: &str, print_plain: impl FnOnce(&str)) {
+        if level >= self.options.console_level {
+            if self.options.json {
+                println!("{}", self.render_json(level, message));
+            } else {
+                print_plain(message);
+            }
+        }
+        if level >= self.options.file_level {
+            self.write_line(&if self.options.json {
+                self.render_json(level, message)
+            } else {
+                message.to_string()
+            });
         }
     }
 
+    fn render_json(&self, level: Level, message: &str) -> String {
+        let unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+        let line = JsonLine {
+            level: level.label(),
+            message,
+            unix_ms,
+        };
+        serde_json::to_string(&line).unwrap_or_else(|_| message.to_string())
+    }
+
     fn write_line(&self, line: &str) {
         if let Ok(mut file) = self.file.lock() {
             let _ = writeln!(file, "{line}");
         }
     }
 }
+
+/// Rotate `prime-agent.log` -> `.1` -> `.2` ... once it exceeds `max_bytes`, dropping anything
+/// past `keep_rotations`.
+fn rotate_if_needed(log_path: &Path, max_bytes: u64, keep_rotations: usize) -> Result<()> {
+    let Ok(metadata) = std::fs::metadata(log_path) else {
+        return Ok(());
+    };
+    if metadata.len() <= max_bytes {
+        return Ok(());
+    }
+
+    let oldest = rotated_path(log_path, keep_rotations);
+    if oldest.exists() {
+        std::fs::remove_file(&oldest)
+            .with_context(|| format!("failed to remove oldest rotation '{}'", oldest.display()))?;
+    }
+    for generation in (1..keep_rotations).rev() {
+        let from = rotated_path(log_path, generation);
+        let to = rotated_path(log_path, generation + 1);
+        if from.exists() {
+            std::fs::rename(&from, &to)
+                .with_context(|| format!("failed to rotate '{}' -> '{}'", from.display(), to.display()))?;
+        }
+    }
+    std::fs::rename(log_path, rotated_path(log_path, 1))
+        .with_context(|| format!("failed to rotate '{}'", log_path.display()))?;
+    Ok(())
+}
+
+fn rotated_path(log_path: &Path, generation: usize) -> PathBuf {
+    let file_name = log_path
+        .file_name()
+        .map_or_else(|| DEFAULT_LOG_FILE.to_string(), |name| name.to_string_lossy().to_string());
+    log_path.with_file_name(format!("{file_name}.{generation}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_and_caps_at_keep_count() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let log_path = dir.path().join("prime-agent.log");
+        std::fs::write(&log_path, vec![0u8; 16]).expect("write log");
+
+        rotate_if_needed(&log_path, 8, 2).expect("rotate once");
+        assert!(dir.path().join("prime-agent.log.1").exists());
+        assert!(!log_path.exists());
+
+        std::fs::write(&log_path, vec![0u8; 16]).expect("write log again");
+        rotate_if_needed(&log_path, 8, 2).expect("rotate twice");
+        assert!(dir.path().join("prime-agent.log.1").exists());
+        assert!(dir.path().join("prime-agent.log.2").exists());
+
+        std::fs::write(&log_path, vec![0u8; 16]).expect("write log thrice");
+        rotate_if_needed(&log_path, 8, 2).expect("rotate thrice, dropping oldest");
+        assert!(dir.path().join("prime-agent.log.1").exists());
+        assert!(dir.path().join("prime-agent.log.2").exists());
+    }
+
+    #[test]
+    fn from_config_applies_configured_log_settings() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut config = Config::default();
+        config.log_dir = Some(dir.path().to_string_lossy().to_string());
+        config.log_max_bytes = Some(4096);
+        config.log_keep_rotations = Some(2);
+        config.log_json = Some(true);
+
+        let logger = Logger::from_config(&config, false).expect("logger from config");
+        assert_eq!(logger.log_path(), dir.path().join("prime-agent.log"));
+    }
+}
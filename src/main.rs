@@ -3,22 +3,45 @@
 
 use anyhow::{anyhow, Context, Result};
 use clap::Parser;
+use std::collections::BTreeMap;
 use std::env;
 use std::path::{Path, PathBuf};
 
 mod agents_md;
 mod cli;
 mod config;
+mod diff;
+mod hooks;
+mod lifecycle;
+mod lock;
+mod logging;
+mod patch;
+mod picker;
+mod plan;
+mod preview;
+mod registry;
+mod report;
 mod skills_store;
+mod state;
+mod steps;
 mod sync;
+mod templates;
+mod vcs;
 
 use crate::agents_md::AgentSection;
-use crate::cli::{Cli, Command, ConfigAction};
+use crate::cli::{Cli, Command, ConfigAction, HookPolicyArg, HooksAction};
 use crate::config::Config;
+use crate::lifecycle::{HookPolicy, RunOptions};
+use crate::logging::Logger;
+use crate::picker::Candidate;
+use crate::plan::Plan;
 use crate::skills_store::SkillsStore;
+use crate::state::StateFile;
+use crate::steps::StepsFile;
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let argv = resolve_argv()?;
+    let cli = Cli::parse_from(argv);
     if should_print_banner(&cli) {
         let version = env!("CARGO_PKG_VERSION");
         println!("\u{001b}[32mprime-agent({version})\u{001b}[0m");
@@ -38,13 +61,17 @@ fn main() -> Result<()> {
     let skills_store = SkillsStore::new(skills_dir);
 
     match cli.command {
-        Command::Get { skills } => {
-            let skill_names = cli::expand_skill_args(skills)?;
+        Command::Get { skills, vars, refresh_vars } => {
+            let cli_vars = cli::parse_var_args(&vars)?;
+            let skill_names =
+                resolve_skill_names(skills, &skills_store, &agents_path, &overrides, PickerSource::AllSkills)?;
             let mut sections = Vec::with_capacity(skill_names.len());
             for name in skill_names {
                 SkillsStore::validate_name(&name)?;
                 let content = skills_store.load_skill(&name)?;
-                sections.push(AgentSection::from_content(name, &content));
+                let rendered_content =
+                    templates::render(skills_store.root(), &name, &content, &cli_vars, &overrides, refresh_vars)?;
+                sections.push(AgentSection::from_content(name, &rendered_content));
             }
             let rendered = agents_md::render_sections(&sections);
             std::fs::write(&agents_path, rendered)?;
@@ -53,65 +80,315 @@ fn main() -> Result<()> {
             SkillsStore::validate_name(&name)?;
             let content = std::fs::read_to_string(&path)?;
             skills_store.save_skill(&name, &content)?;
+            if !cli.no_lock {
+                let mut lock = lock::SkillLock::load(skills_store.root());
+                let source_path = skills_store.root().join(&name).join("SKILL.md");
+                lock.record(&name, &source_path, &content, None);
+                lock.save()?;
+            }
+        }
+        Command::Add { source } => {
+            let name = source
+                .rsplit('/')
+                .next()
+                .map(|name| name.trim_end_matches(".md"))
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| anyhow!("could not determine skill name from source '{source}'"))?;
+            SkillsStore::validate_name(name)?;
+            let config_path = config::config_path()?;
+            let config = if config_path.exists() {
+                Config::load_required(&config_path)?
+            } else {
+                Config::default()
+            };
+            let content = registry::fetch_skill(&source, &config.registries)?;
+            skills_store.save_skill(name, &content)?;
+        }
+        Command::Sync => {
+            run_sync_cmd(&skills_store, &agents_path)?;
+            if !cli.no_lock {
+                sync_skill_lock(&skills_store, &agents_path)?;
+            }
+        }
+        Command::SyncRemote => {
+            run_sync_remote_cmd(&skills_store, &agents_path, &overrides)?;
+            if !cli.no_lock {
+                sync_skill_lock(&skills_store, &agents_path)?;
+            }
         }
-        Command::Sync => run_sync_cmd(&skills_store, &agents_path)?,
-        Command::SyncRemote => run_sync_remote_cmd(&skills_store, &agents_path)?,
-        Command::List { fragment } => run_list_cmd(&skills_store, fragment)?,
-        Command::Local => run_local_cmd(&skills_store, &agents_path)?,
+        Command::List { fragment } => run_list_cmd(&skills_store, fragment, &overrides)?,
+        Command::Local => run_local_cmd(&skills_store, &agents_path, &overrides, cli.no_lock)?,
         Command::Config { .. } => {
             unreachable!("config command handled before skills setup");
         }
-        Command::Delete { name } => {
-            SkillsStore::validate_name(&name)?;
+        Command::Delete { names } => {
+            let names =
+                resolve_skill_names(names, &skills_store, &agents_path, &overrides, PickerSource::AgentsSections)?;
             let contents = std::fs::read_to_string(&agents_path)
                 .with_context(|| format!("failed to read '{}'", agents_path.display()))?;
             let mut doc = agents_md::AgentsDoc::parse(&contents)?;
-            if doc.remove_section(&name) {
+            let mut changed = false;
+            let mut lock = (!cli.no_lock).then(|| lock::SkillLock::load(skills_store.root()));
+            for name in names {
+                SkillsStore::validate_name(&name)?;
+                changed |= doc.remove_section(&name);
+                if let Some(lock) = lock.as_mut() {
+                    lock.clear_agents_hash(&name);
+                }
+            }
+            if changed {
                 std::fs::write(&agents_path, doc.render())
                     .with_context(|| format!("failed to write '{}'", agents_path.display()))?;
             }
+            if let Some(lock) = lock.as_mut() {
+                lock.save()?;
+            }
         }
-        Command::DeleteGlobally { name } => {
-            SkillsStore::validate_name(&name)?;
+        Command::DeleteGlobally { names } => {
+            let names =
+                resolve_skill_names(names, &skills_store, &agents_path, &overrides, PickerSource::AgentsSections)?;
             let contents = std::fs::read_to_string(&agents_path)
                 .with_context(|| format!("failed to read '{}'", agents_path.display()))?;
             let mut doc = agents_md::AgentsDoc::parse(&contents)?;
-            if doc.remove_section(&name) {
-                std::fs::write(&agents_path, doc.render())
-                    .with_context(|| format!("failed to write '{}'", agents_path.display()))?;
+            let mut lock = (!cli.no_lock).then(|| lock::SkillLock::load(skills_store.root()));
+            for name in names {
+                SkillsStore::validate_name(&name)?;
+                if doc.remove_section(&name) {
+                    std::fs::write(&agents_path, doc.render()).with_context(|| {
+                        format!("failed to write '{}'", agents_path.display())
+                    })?;
+                }
+                skills_store.delete_skill(&name)?;
+                if let Some(lock) = lock.as_mut() {
+                    lock.remove(&name);
+                }
+            }
+            if let Some(lock) = lock.as_mut() {
+                lock.save()?;
+            }
+        }
+        Command::Preview { name, no_color } => {
+            SkillsStore::validate_name(&name)?;
+            let content = skills_store.load_skill(&name)?;
+            let theme = overrides.get("theme").cloned().unwrap_or_else(|| "dark".to_string());
+            let color = preview::should_use_color(no_color);
+            println!("{}", preview::render(&content, &theme, color)?);
+        }
+        Command::Diff { name } => diff::run_diff(&skills_store, &agents_path, name.as_deref())?,
+        Command::Hooks { action } => {
+            let repo_root = std::env::current_dir().context("failed to resolve current directory")?;
+            match action {
+                HooksAction::Install { force } => {
+                    hooks::install(&repo_root, skills_store.root(), &agents_path, force)?;
+                }
+                HooksAction::Uninstall => {
+                    hooks::uninstall(&repo_root)?;
+                }
             }
-            skills_store.delete_skill(&name)?;
+        }
+        Command::Run { plan, state, workdir, hook_policy, report, watch } => {
+            run_lifecycle_cmd(&plan, &state, workdir.as_deref(), hook_policy, &report, watch, &overrides)?;
         }
     }
     Ok(())
 }
 
+impl From<HookPolicyArg> for HookPolicy {
+    fn from(value: HookPolicyArg) -> Self {
+        match value {
+            HookPolicyArg::Run => HookPolicy::Run,
+            HookPolicyArg::Skip => HookPolicy::Skip,
+            HookPolicyArg::RunAndCapture => HookPolicy::RunAndCapture,
+        }
+    }
+}
+
+/// Drive an agent lifecycle plan to completion (or until a gate pauses it for review), or keep
+/// re-running its gates on every file change when `watch` is set.
+fn run_lifecycle_cmd(
+    plan_path: &Path,
+    state_path: &Path,
+    workdir: Option<&Path>,
+    hook_policy: HookPolicyArg,
+    report_path: &Path,
+    watch: bool,
+    overrides: &std::collections::HashMap<String, String>,
+) -> Result<()> {
+    let cwd = env::current_dir().context("failed to resolve current directory")?;
+    let mut config = Config::discover(&cwd)?;
+    config.apply_overrides(overrides);
+    let logger = Logger::from_config(&config, false)?;
+
+    let options = RunOptions::new(
+        plan_path.to_path_buf(),
+        state_path.to_path_buf(),
+        workdir,
+        hook_policy.into(),
+        report_path.to_path_buf(),
+    )?;
+
+    if watch {
+        return lifecycle::watch(&config, &options, &logger);
+    }
+
+    let plan = Plan::load(plan_path)?;
+    let (steps, _) = StepsFile::load_or_sync(plan_path, &plan)?;
+    let mut state_file = if state_path.exists() {
+        StateFile::load(state_path)?
+    } else {
+        StateFile::default()
+    };
+
+    lifecycle::run_to_completion(&config, &plan, &steps, &mut state_file, &options, &logger)
+}
+
+/// Where the interactive picker's candidate list comes from.
+#[derive(Clone, Copy)]
+enum PickerSource {
+    /// Every skill in the store (used by `get`, where the picker also doubles as an "update
+    /// selection" view over what's already in AGENTS.md).
+    AllSkills,
+    /// Only the sections currently present in AGENTS.md (used by `delete`/`delete-globally`).
+    AgentsSections,
+}
+
+/// Resolve the skill names an invocation should act on, launching the interactive picker when
+/// `args` is empty or a bare `-` and stdin is a TTY.
+fn resolve_skill_names(
+    args: Vec<String>,
+    skills_store: &SkillsStore,
+    agents_path: &Path,
+    overrides: &std::collections::HashMap<String, String>,
+    source: PickerSource,
+) -> Result<Vec<String>> {
+    if cli::wants_interactive_picker(&args) && picker::stdin_is_tty() {
+        let agents_doc = if agents_path.exists() {
+            let contents = std::fs::read_to_string(agents_path)
+                .with_context(|| format!("failed to read '{}'", agents_path.display()))?;
+            Some(agents_md::AgentsDoc::parse(&contents)?)
+        } else {
+            None
+        };
+        let present_sections: std::collections::HashSet<String> = agents_doc
+            .as_ref()
+            .map(|doc| doc.section_names().into_iter().collect())
+            .unwrap_or_default();
+        let statuses = sync::compute_sync_status(skills_store, agents_doc.as_ref())?;
+        let names = match source {
+            PickerSource::AllSkills => skills_store.list_skill_names()?,
+            PickerSource::AgentsSections => agents_doc.as_ref().map_or_else(Vec::new, agents_md::AgentsDoc::section_names),
+        };
+        let candidates = names
+            .into_iter()
+            .map(|name| {
+                let status = statuses
+                    .get(&name)
+                    .map_or_else(|| "in-sync".to_string(), |status| format!("{status:?}"));
+                // For `get`, pre-check skills already present in AGENTS.md; for `delete`, always
+                // start unchecked so a blank selection doesn't accidentally remove everything.
+                let preselected = matches!(source, PickerSource::AllSkills) && present_sections.contains(&name);
+                Candidate { name, status, preselected }
+            })
+            .collect::<Vec<_>>();
+        let finder_program = overrides.get("finder-program").cloned();
+        return picker::pick_skills(&candidates, finder_program.as_deref());
+    }
+    cli::expand_skill_args(args)
+}
+
+/// Peek at the raw argv and splice in any configured alias before clap ever sees it.
+fn resolve_argv() -> Result<Vec<String>> {
+    let argv: Vec<String> = env::args().collect();
+    let config_path = config::config_path()?;
+    let config = if config_path.exists() {
+        Config::load_required(&config_path)?
+    } else {
+        Config::default()
+    };
+    cli::expand_aliases(argv, &config.aliases)
+}
+
 fn run_sync_cmd(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
     sync::run_sync(skills_store, agents_path)
 }
 
-fn run_sync_remote_cmd(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
-    sync::run_sync_remote(skills_store, agents_path)
+/// Refresh `.prime-agent.lock` after a successful `sync`/`sync-remote`: record every skill
+/// currently in `AGENTS.md` against its (now-reconciled) content and section hash.
+fn sync_skill_lock(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
+    let mut skill_lock = lock::SkillLock::load(skills_store.root());
+    if skill_lock.is_empty() && !skills_store.list_skill_names()?.is_empty() {
+        skill_lock = lock::SkillLock::rebuild(skills_store)?;
+    }
+    if agents_path.exists() {
+        let contents = std::fs::read_to_string(agents_path)
+            .with_context(|| format!("failed to read '{}'", agents_path.display()))?;
+        let doc = agents_md::AgentsDoc::parse(&contents)?;
+        for name in doc.section_names() {
+            if !skills_store.skill_exists(&name) {
+                continue;
+            }
+            let Some(section) = doc.get_section(&name) else {
+                continue;
+            };
+            let content = skills_store.load_skill(&name)?;
+            let source_path = skills_store.root().join(&name).join("SKILL.md");
+            skill_lock.record(&name, &source_path, &content, Some(&section.content_string()));
+        }
+    }
+    skill_lock.save()
+}
+
+fn run_sync_remote_cmd(
+    skills_store: &SkillsStore,
+    agents_path: &Path,
+    overrides: &std::collections::HashMap<String, String>,
+) -> Result<()> {
+    let registry_url = resolve_registry_url(overrides)?;
+    let registry_token = resolve_registry_token(overrides)?;
+    sync::run_sync_remote(skills_store, agents_path, registry_url.as_deref(), registry_token.as_deref())
 }
 
-fn run_list_cmd(skills_store: &SkillsStore, fragment: Option<String>) -> Result<()> {
+fn run_list_cmd(
+    skills_store: &SkillsStore,
+    fragment: Option<String>,
+    overrides: &std::collections::HashMap<String, String>,
+) -> Result<()> {
     let mut skills = skills_store.list_skill_names()?;
     if let Some(fragment) = fragment {
         skills.retain(|name| name.contains(&fragment));
         println!("{}", skills.join(" "));
-    } else {
-        let mut first = true;
-        for name in skills {
-            if !first {
-                println!();
-            }
-            first = false;
-            println!("{name}");
+        return Ok(());
+    }
+    let registry_statuses = match resolve_registry_url(overrides)? {
+        Some(url) => {
+            let token = resolve_registry_token(overrides)?;
+            Some(sync::compute_registry_status(skills_store, &url, token.as_deref())?)
+        }
+        None => None,
+    };
+    let mut first = true;
+    for name in skills {
+        if !first {
+            println!();
         }
+        first = false;
+        println!("{name}");
+        print_registry_status(registry_statuses.as_ref(), &name);
     }
     Ok(())
 }
 
+/// Print an extra indented line noting a skill's registry ahead/behind/conflict status, if a
+/// registry is configured and the skill isn't already in sync with it.
+fn print_registry_status(statuses: Option<&BTreeMap<String, sync::RegistryStatus>>, name: &str) {
+    match statuses.and_then(|statuses| statuses.get(name)) {
+        Some(sync::RegistryStatus::Ahead) => println!("  ahead of registry"),
+        Some(sync::RegistryStatus::Behind) => println!("  behind registry"),
+        Some(sync::RegistryStatus::Conflict) => println!("  diverged from registry"),
+        Some(sync::RegistryStatus::InSync) | None => {}
+    }
+}
+
 #[allow(clippy::missing_const_for_fn)]
 fn should_print_banner(cli: &Cli) -> bool {
     !matches!(
@@ -122,7 +399,38 @@ fn should_print_banner(cli: &Cli) -> bool {
     )
 }
 
-fn run_local_cmd(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
+/// Sync status for every section in `AGENTS.md`, preferring the lock-based comparison
+/// (`.prime-agent.lock`'s recorded hashes against the current skill and section content) and falling back to
+/// the full comparison when `--no-lock` was passed or the lock doesn't cover every section yet
+/// (e.g. it's missing or stale).
+fn local_sync_statuses(
+    skills_store: &SkillsStore,
+    doc: &agents_md::AgentsDoc,
+    section_names: &[String],
+    no_lock: bool,
+) -> Result<BTreeMap<String, sync::SyncStatus>> {
+    if no_lock {
+        return sync::compute_sync_status(skills_store, Some(doc));
+    }
+    let sections: BTreeMap<String, String> = section_names
+        .iter()
+        .filter_map(|name| doc.get_section(name).map(|section| (name.clone(), section.content_string())))
+        .collect();
+    let skill_lock = lock::SkillLock::load(skills_store.root());
+    let lock_statuses = skill_lock.compute_status(skills_store, &sections)?;
+    if section_names.iter().all(|name| lock_statuses.contains_key(name)) {
+        Ok(lock_statuses)
+    } else {
+        sync::compute_sync_status(skills_store, Some(doc))
+    }
+}
+
+fn run_local_cmd(
+    skills_store: &SkillsStore,
+    agents_path: &Path,
+    overrides: &std::collections::HashMap<String, String>,
+    no_lock: bool,
+) -> Result<()> {
     let agents_doc = if agents_path.exists() {
         let contents = std::fs::read_to_string(agents_path)
             .with_context(|| format!("failed to read '{}'", agents_path.display()))?;
@@ -137,7 +445,14 @@ fn run_local_cmd(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
     if section_names.is_empty() {
         return Ok(());
     }
-    let statuses = sync::compute_sync_status(skills_store, agents_doc.as_ref())?;
+    let statuses = local_sync_statuses(skills_store, doc, &section_names, no_lock)?;
+    let registry_statuses = match resolve_registry_url(overrides)? {
+        Some(url) => {
+            let token = resolve_registry_token(overrides)?;
+            Some(sync::compute_registry_status(skills_store, &url, token.as_deref())?)
+        }
+        None => None,
+    };
     for name in section_names {
         match statuses.get(&name) {
             Some(sync::SyncStatus::Local) => {
@@ -153,6 +468,7 @@ fn run_local_cmd(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
                 println!("{name}");
             }
         }
+        print_registry_status(registry_statuses.as_ref(), &name);
     }
     Ok(())
 }
@@ -197,18 +513,43 @@ fn resolve_skills_dir(
     if let Ok(env_path) = env::var("PRIME_AGENT_SKILLS_DIR") {
         return Ok(expand_path(Path::new(&env_path)));
     }
-    let config_path = config::config_path()?;
-    let mut config = if config_path.exists() {
-        Config::load_required(&config_path)?
-    } else {
-        Config::default()
-    };
+    let cwd = env::current_dir().context("failed to resolve current directory")?;
+    let mut config = Config::discover(&cwd)?;
     config.apply_overrides(overrides);
     config
         .skills_dir()
         .context("skills directory not configured; use --skills-dir or config file")
 }
 
+/// Resolve the `registry-url` setting: `--config registry-url:...` override, then
+/// `PRIME_AGENT_REGISTRY_URL`, then the layered config file. Unlike `skills-dir` this is
+/// optional, so a missing value is `Ok(None)` rather than an error.
+fn resolve_registry_url(overrides: &std::collections::HashMap<String, String>) -> Result<Option<String>> {
+    resolve_optional_setting("registry-url", "PRIME_AGENT_REGISTRY_URL", overrides)
+}
+
+/// Resolve the `registry-token` setting, with the same precedence as [`resolve_registry_url`].
+fn resolve_registry_token(overrides: &std::collections::HashMap<String, String>) -> Result<Option<String>> {
+    resolve_optional_setting("registry-token", "PRIME_AGENT_REGISTRY_TOKEN", overrides)
+}
+
+fn resolve_optional_setting(
+    key: &str,
+    env_var: &str,
+    overrides: &std::collections::HashMap<String, String>,
+) -> Result<Option<String>> {
+    if let Some(value) = overrides.get(key).cloned() {
+        return Ok(Some(value));
+    }
+    if let Ok(value) = env::var(env_var) {
+        return Ok(Some(value));
+    }
+    let cwd = env::current_dir().context("failed to resolve current directory")?;
+    let mut config = Config::discover(&cwd)?;
+    config.apply_overrides(overrides);
+    Ok(config.get_value(key))
+}
+
 fn parse_config_overrides(values: &[String]) -> Result<std::collections::HashMap<String, String>> {
     let mut overrides = std::collections::HashMap::new();
     for value in values {
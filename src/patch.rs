@@ -0,0 +1,281 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::logging::Logger;
+
+/// Maximum number of lines a hunk's recorded position may have drifted by (additions/removals
+/// elsewhere in the file) before we give up looking for its context.
+const MAX_FUZZ: usize = 20;
+
+/// One line inside a hunk, tagged by how it participates in the old/new file content.
+#[derive(Debug, Clone)]
+enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+/// A single `@@ -a,b +c,d @@` hunk plus its body lines.
+#[derive(Debug, Clone)]
+struct Hunk {
+    header: String,
+    old_start: usize,
+    lines: Vec<HunkLine>,
+}
+
+/// All hunks targeting one file, as found under a `--- a/<path>` / `+++ b/<path>` pair.
+#[derive(Debug, Clone)]
+struct FilePatch {
+    path: String,
+    hunks: Vec<Hunk>,
+}
+
+/// A hunk that could not be applied because its context no longer matches the file.
+#[derive(Debug, Clone)]
+pub struct HunkFailure {
+    pub file: String,
+    pub header: String,
+    pub reason: String,
+}
+
+/// Outcome of applying a patch: how many hunks succeeded, and which ones didn't.
+#[derive(Debug, Default)]
+pub struct PatchResult {
+    pub applied_hunks: usize,
+    pub failed_hunks: Vec<HunkFailure>,
+}
+
+impl PatchResult {
+    #[must_use]
+    pub fn all_applied(&self) -> bool {
+        self.failed_hunks.is_empty()
+    }
+}
+
+/// Parse `patch_text` as a unified diff and apply every hunk it contains to files under
+/// `workdir`, logging each applied or rejected hunk through `logger`. A hunk whose context can't
+/// be matched (even allowing fuzz) is skipped and reported rather than corrupting the file; hunks
+/// that do match are still applied, so a partially-matching patch makes partial progress.
+///
+/// # Errors
+///
+/// Returns an error if a target file cannot be read or the patched result cannot be written back.
+pub fn apply_patch(workdir: &Path, patch_text: &str, logger: &Logger) -> Result<PatchResult> {
+    let mut result = PatchResult::default();
+    for file_patch in parse_patch(patch_text) {
+        let target = workdir.join(&file_patch.path);
+        let original = fs_read(&target)?;
+        let had_trailing_newline = original.ends_with('\n');
+        let mut lines: Vec<String> = original.lines().map(str::to_string).collect();
+
+        let mut offset: isize = 0;
+        for hunk in &file_patch.hunks {
+            let expected_start = (hunk.old_start.saturating_sub(1) as isize + offset).max(0) as usize;
+            match locate_and_apply(&mut lines, hunk, expected_start) {
+                Ok(delta) => {
+                    offset += delta;
+                    result.applied_hunks += 1;
+                    logger.log_substep(&format!(
+                        "Applied hunk {} to {}",
+                        hunk.header, file_patch.path
+                    ));
+                }
+                Err(reason) => {
+                    logger.log_error(&format!(
+                        "Rejected hunk {} in {}: {reason}",
+                        hunk.header, file_patch.path
+                    ));
+                    result.failed_hunks.push(HunkFailure {
+                        file: file_patch.path.clone(),
+                        header: hunk.header.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        let mut contents = lines.join("\n");
+        if had_trailing_newline && !contents.is_empty() {
+            contents.push('\n');
+        }
+        std::fs::write(&target, contents)
+            .with_context(|| format!("failed to write patched file: {}", target.display()))?;
+    }
+    Ok(result)
+}
+
+fn fs_read(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("failed to read file to patch: {}", path.display()))
+}
+
+/// Find where `hunk`'s old context/remove lines occur in `lines`, searching outward from
+/// `expected_start` by up to [`MAX_FUZZ`] lines in either direction, then splice in the new
+/// content. Returns the net change in line count (for adjusting later hunks' expected positions).
+fn locate_and_apply(lines: &mut Vec<String>, hunk: &Hunk, expected_start: usize) -> Result<isize, String> {
+    let old_seq: Vec<&str> = hunk
+        .lines
+        .iter()
+        .filter_map(|line| match line {
+            HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+            HunkLine::Add(_) => None,
+        })
+        .collect();
+    let new_seq: Vec<String> = hunk
+        .lines
+        .iter()
+        .filter_map(|line| match line {
+            HunkLine::Context(text) => Some(text.clone()),
+            HunkLine::Add(text) => Some(text.clone()),
+            HunkLine::Remove(_) => None,
+        })
+        .collect();
+
+    let start = find_context(lines, &old_seq, expected_start)
+        .ok_or_else(|| "context did not match file content within fuzz range".to_string())?;
+
+    let old_len = old_seq.len();
+    let new_len = new_seq.len();
+    lines.splice(start..start + old_len, new_seq);
+    Ok(new_len as isize - old_len as isize)
+}
+
+/// Search for `old_seq` as a contiguous run in `lines`, starting at `expected_start` and then
+/// trying successively farther offsets (0, +1, -1, +2, -2, ...) up to [`MAX_FUZZ`].
+fn find_context(lines: &[String], old_seq: &[&str], expected_start: usize) -> Option<usize> {
+    if old_seq.is_empty() {
+        return Some(expected_start.min(lines.len()));
+    }
+    for delta in 0..=MAX_FUZZ {
+        for candidate in candidate_positions(expected_start, delta) {
+            if candidate + old_seq.len() > lines.len() {
+                continue;
+            }
+            if lines[candidate..candidate + old_seq.len()]
+                .iter()
+                .map(String::as_str)
+                .eq(old_seq.iter().copied())
+            {
+                return Some(candidate);
+            }
+        }
+    }
+    None
+}
+
+fn candidate_positions(expected_start: usize, delta: usize) -> Vec<usize> {
+    if delta == 0 {
+        return vec![expected_start];
+    }
+    let mut positions = Vec::with_capacity(2);
+    positions.push(expected_start + delta);
+    if let Some(below) = expected_start.checked_sub(delta) {
+        positions.push(below);
+    }
+    positions
+}
+
+/// Split unified-diff text into per-file hunk groups, keyed by the `+++ b/<path>` target path.
+fn parse_patch(patch_text: &str) -> Vec<FilePatch> {
+    let mut files = Vec::new();
+    let mut current: Option<FilePatch> = None;
+    let mut current_hunk: Option<Hunk> = None;
+
+    for line in patch_text.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(hunk) = current_hunk.take() {
+                if let Some(file) = current.as_mut() {
+                    file.hunks.push(hunk);
+                }
+            }
+            if let Some(file) = current.take() {
+                files.push(file);
+            }
+            current = Some(FilePatch {
+                path: normalize_diff_path(path),
+                hunks: Vec::new(),
+            });
+        } else if line.starts_with("--- ") {
+            // Old-file marker; the path we care about comes from the following `+++` line.
+            continue;
+        } else if let Some(header_rest) = line.strip_prefix("@@ ") {
+            if let Some(hunk) = current_hunk.take() {
+                if let Some(file) = current.as_mut() {
+                    file.hunks.push(hunk);
+                }
+            }
+            if let Some(old_start) = parse_hunk_old_start(header_rest) {
+                current_hunk = Some(Hunk {
+                    header: format!("@@ {header_rest}"),
+                    old_start,
+                    lines: Vec::new(),
+                });
+            }
+        } else if let Some(hunk) = current_hunk.as_mut() {
+            if let Some(text) = line.strip_prefix('+') {
+                hunk.lines.push(HunkLine::Add(text.to_string()));
+            } else if let Some(text) = line.strip_prefix('-') {
+                hunk.lines.push(HunkLine::Remove(text.to_string()));
+            } else if let Some(text) = line.strip_prefix(' ') {
+                hunk.lines.push(HunkLine::Context(text.to_string()));
+            } else if line.is_empty() {
+                hunk.lines.push(HunkLine::Context(String::new()));
+            }
+        }
+    }
+
+    if let Some(hunk) = current_hunk.take() {
+        if let Some(file) = current.as_mut() {
+            file.hunks.push(hunk);
+        }
+    }
+    if let Some(file) = current.take() {
+        files.push(file);
+    }
+    files
+}
+
+/// Strip a leading `a/`/`b/` prefix (as `git diff` emits) from a diff header path.
+fn normalize_diff_path(path: &str) -> String {
+    let path = path.split('\t').next().unwrap_or(path).trim();
+    path.strip_prefix("b/").or_else(|| path.strip_prefix("a/")).unwrap_or(path).to_string()
+}
+
+/// Parse the old-file start line out of a hunk header's remainder, e.g. `-12,5 +12,7 @@` -> `12`.
+fn parse_hunk_old_start(header_rest: &str) -> Option<usize> {
+    let old_part = header_rest.split(' ').next()?;
+    let digits = old_part.strip_prefix('-')?.split(',').next()?;
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_a_single_hunk_in_place() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("greeting.txt"), "hello\nworld\nfoo\n").expect("write file");
+        let logger = Logger::new(false).expect("logger");
+
+        let patch = "--- a/greeting.txt\n+++ b/greeting.txt\n@@ -1,3 +1,3 @@\n hello\n-world\n+rust\n foo\n";
+        let result = apply_patch(dir.path(), patch, &logger).expect("apply patch");
+
+        assert_eq!(result.applied_hunks, 1);
+        assert!(result.all_applied());
+        let contents = std::fs::read_to_string(dir.path().join("greeting.txt")).expect("read back");
+        assert_eq!(contents, "hello\nrust\nfoo\n");
+    }
+
+    #[test]
+    fn rejects_a_hunk_whose_context_has_drifted_too_far() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("greeting.txt"), "completely\ndifferent\ncontent\n").expect("write file");
+        let logger = Logger::new(false).expect("logger");
+
+        let patch = "--- a/greeting.txt\n+++ b/greeting.txt\n@@ -1,3 +1,3 @@\n hello\n-world\n+rust\n foo\n";
+        let result = apply_patch(dir.path(), patch, &logger).expect("apply patch");
+
+        assert_eq!(result.applied_hunks, 0);
+        assert_eq!(result.failed_hunks.len(), 1);
+    }
+}
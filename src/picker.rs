@@ -0,0 +1,210 @@
+use anyhow::{Context, Result};
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// A candidate shown in the interactive picker: a skill name plus its sync status label.
+pub struct Candidate {
+    pub name: String,
+    pub status: String,
+    /// Whether this candidate should be checked off by default (e.g. already present in the
+    /// target AGENTS.md), so a blank selection keeps the current set instead of picking nothing.
+    pub preselected: bool,
+}
+
+/// Whether an interactive picker should be offered for the current process.
+#[must_use]
+pub fn stdin_is_tty() -> bool {
+    std::io::stdin().is_terminal()
+}
+
+/// Let the user pick one or more skills from `candidates`.
+///
+/// Tries an external finder program first (`finder_program`, falling back to `fzf`), piping
+/// `name\t[ ] status` lines on stdin and reading the selected lines back on stdout. If no external
+/// finder is found on `PATH`, falls back to a built-in subsequence fuzzy matcher driven by a
+/// single query read from stdin.
+///
+/// An external finder has no supported way to pre-select rows fed to it over a pipe, so when any
+/// candidate is `preselected` (e.g. `get` pre-checking sections already in AGENTS.md) this skips
+/// straight to the builtin picker, which does honor it, rather than silently losing the defaults.
+///
+/// # Errors
+///
+/// Returns an error if stdin/stdout cannot be read, or the external finder exits with failure.
+pub fn pick_skills(candidates: &[Candidate], finder_program: Option<&str>) -> Result<Vec<String>> {
+    if candidates.iter().any(|candidate| candidate.preselected) {
+        return run_builtin_picker(candidates);
+    }
+    let program = finder_program.unwrap_or("fzf");
+    match run_external_finder(program, candidates) {
+        Ok(selected) => Ok(selected),
+        Err(FinderError::NotFound) => run_builtin_picker(candidates),
+        Err(FinderError::Other(err)) => Err(err),
+    }
+}
+
+enum FinderError {
+    NotFound,
+    Other(anyhow::Error),
+}
+
+fn run_external_finder(program: &str, candidates: &[Candidate]) -> Result<Vec<String>, FinderError> {
+    let mut child = Command::new(program)
+        .arg("--multi")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                FinderError::NotFound
+            } else {
+                FinderError::Other(anyhow::anyhow!(err).context(format!("failed to launch {program}")))
+            }
+        })?;
+
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| FinderError::Other(anyhow::anyhow!("missing finder stdin")))?;
+        for candidate in candidates {
+            let marker = if candidate.preselected { "[x]" } else { "[ ]" };
+            writeln!(stdin, "{}\t{marker} {}", candidate.name, candidate.status)
+                .map_err(|err| FinderError::Other(anyhow::anyhow!(err)))?;
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| FinderError::Other(anyhow::anyhow!(err).context("failed waiting for finder")))?;
+    if !output.status.success() {
+        return Err(FinderError::Other(anyhow::anyhow!(
+            "{program} exited with {}",
+            output.status
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.split('\t').next())
+        .map(str::to_string)
+        .collect())
+}
+
+fn run_builtin_picker(candidates: &[Candidate]) -> Result<Vec<String>> {
+    print!("Search skills (subsequence match): ");
+    std::io::stdout().flush().ok();
+    let mut query = String::new();
+    std::io::stdin()
+        .read_line(&mut query)
+        .context("failed to read picker query")?;
+    let query = query.trim();
+
+    let mut scored: Vec<(i64, &Candidate)> = candidates
+        .iter()
+        .filter_map(|candidate| score_match(&candidate.name, query).map(|score| (score, candidate)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+    for (rank, (score, candidate)) in scored.iter().enumerate() {
+        let marker = if candidate.preselected { "[x]" } else { "[ ]" };
+        println!("{}. {marker} {} (score {score})", rank + 1, candidate.name);
+    }
+    if scored.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let preselected_names: Vec<String> = scored
+        .iter()
+        .filter(|(_, candidate)| candidate.preselected)
+        .map(|(_, candidate)| candidate.name.clone())
+        .collect();
+
+    print!("Select (comma-separated numbers, blank to keep the checked defaults): ");
+    std::io::stdout().flush().ok();
+    let mut selection = String::new();
+    std::io::stdin()
+        .read_line(&mut selection)
+        .context("failed to read picker selection")?;
+    let selection = selection.trim();
+    if selection.is_empty() {
+        if preselected_names.is_empty() {
+            return Ok(vec![scored[0].1.name.clone()]);
+        }
+        return Ok(preselected_names);
+    }
+
+    let mut picked = Vec::new();
+    for token in selection.split(',') {
+        let index: usize = token.trim().parse().context("invalid picker selection")?;
+        if let Some((_, candidate)) = scored.get(index.saturating_sub(1)) {
+            picked.push(candidate.name.clone());
+        }
+    }
+    Ok(picked)
+}
+
+/// Score `candidate` against `query` via in-order subsequence matching.
+///
+/// Every character of `query` must appear in `candidate`, in order. Matches at a word boundary
+/// (start of string, or just after `-`/`_`) and consecutive runs of matched characters each earn
+/// a bonus, so `"scc"` ranks `skills-capybara-config` above an unrelated path containing the
+/// same letters out of order. Returns `None` if `query` does not match as a subsequence.
+fn score_match(candidate: &str, query: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let query_chars: Vec<char> = query.chars().collect();
+
+    let mut score: i64 = 0;
+    let mut candidate_index = 0;
+    let mut query_index = 0;
+    let mut previous_matched = false;
+
+    while candidate_index < candidate_chars.len() && query_index < query_chars.len() {
+        let candidate_char = candidate_chars[candidate_index].to_ascii_lowercase();
+        let query_char = query_chars[query_index].to_ascii_lowercase();
+        if candidate_char == query_char {
+            score += 1;
+            let at_word_boundary = candidate_index == 0
+                || matches!(candidate_chars[candidate_index - 1], '-' | '_');
+            if at_word_boundary {
+                score += 5;
+            }
+            if previous_matched {
+                score += 3;
+            }
+            previous_matched = true;
+            query_index += 1;
+        } else {
+            previous_matched = false;
+        }
+        candidate_index += 1;
+    }
+
+    if query_index == query_chars.len() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_in_order_subsequence() {
+        assert!(score_match("skills-capybara-config", "scc").is_some());
+        assert!(score_match("abc", "cab").is_none());
+    }
+
+    #[test]
+    fn ranks_word_boundary_matches_higher() {
+        let boundary = score_match("release-notes", "rn").unwrap();
+        let mid_word = score_match("return-notes", "rn").unwrap();
+        assert!(boundary > mid_word);
+    }
+}
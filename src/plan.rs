@@ -1,34 +1,76 @@
 use anyhow::{anyhow, Context, Result};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
 use regex::Regex;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::Duration;
 
 use crate::state::{StateFile, StepState};
 
+/// How long to wait after the most recent filesystem event before reloading, so a single editor
+/// save (which can emit several create/modify/rename events back to back) triggers one reload
+/// instead of several.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
 /// Parsed plan with ordered steps.
 #[derive(Debug, Clone)]
 pub struct Plan {
     pub steps: Vec<PlanStep>,
 }
 
-/// A single plan step parsed from Markdown.
-#[derive(Debug, Clone)]
+/// A single plan step, parsed either from a numbered Markdown line or a structured `prime` block.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PlanStep {
     pub id: String,
     pub number: usize,
     pub text: String,
+    /// Free-form key/value parameters attached to a structured step (e.g. `table = "users"`).
+    /// Always empty for steps parsed from the plain numbered-line format.
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+    /// IDs of steps that must reach [`StepState::ImplementedCommitted`] before this one can run.
+    /// Always empty for steps parsed from the plain numbered-line format.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// The contents of a fenced ` ```prime ` code block: a structured, non-linear alternative to the
+/// `1. text` Markdown format, carrying stable IDs, parameters, and inter-step dependencies.
+#[derive(Debug, Deserialize)]
+struct StructuredPlan {
+    steps: Vec<StructuredStep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StructuredStep {
+    id: String,
+    text: String,
+    #[serde(default)]
+    params: HashMap<String, String>,
+    #[serde(default)]
+    depends_on: Vec<String>,
 }
 
 impl Plan {
-    /// Load and parse a Markdown plan file.
+    /// Load and parse a Markdown plan file. If it contains a fenced ` ```prime ` block, that block
+    /// is parsed as a structured, dependency-aware plan; otherwise the file is parsed as a flat
+    /// list of `1. text` lines.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read or no steps are found.
+    /// Returns an error if the file cannot be read, no steps are found, a structured block fails
+    /// to parse, or a structured plan's dependencies are invalid (unknown step or a cycle).
     pub fn load(path: &Path) -> Result<Self> {
         let contents = fs::read_to_string(path)
             .with_context(|| format!("failed to read plan file: {}", path.display()))?;
+
+        if let Some(block) = extract_prime_block(&contents) {
+            return Self::load_structured(block);
+        }
+
         let step_re = Regex::new(r"^\s*(\d+)\.\s+(.+?)\s*$")
             .context("failed to compile plan step regex")?;
         let mut steps = Vec::new();
@@ -45,6 +87,8 @@ impl Plan {
                     id: number.to_string(),
                     number,
                     text,
+                    params: HashMap::new(),
+                    depends_on: Vec::new(),
                 });
             }
         }
@@ -56,17 +100,182 @@ impl Plan {
         Ok(Self { steps })
     }
 
-    /// Return the next step matching the desired state.
+    /// Parse a structured plan's TOML body (the contents of its ` ```prime ` block) into steps,
+    /// assigning sequential display numbers and validating that `depends_on` forms an acyclic
+    /// graph over known step IDs.
+    fn load_structured(block: &str) -> Result<Self> {
+        let parsed: StructuredPlan =
+            toml::from_str(block).context("failed to parse structured plan block")?;
+
+        if parsed.steps.is_empty() {
+            return Err(anyhow!("no steps found in structured plan block"));
+        }
+
+        let mut seen_ids = HashSet::new();
+        let mut steps = Vec::with_capacity(parsed.steps.len());
+        for (index, step) in parsed.steps.into_iter().enumerate() {
+            if !seen_ids.insert(step.id.clone()) {
+                return Err(anyhow!("duplicate plan step id: {}", step.id));
+            }
+            steps.push(PlanStep {
+                id: step.id,
+                number: index + 1,
+                text: step.text,
+                params: step.params,
+                depends_on: step.depends_on,
+            });
+        }
+
+        validate_dependency_dag(&steps)?;
+        Ok(Self { steps })
+    }
+
+    /// Return the next step matching the desired state, skipping any step whose `depends_on` are
+    /// not all committed yet (always true for the empty `depends_on` of a plain numbered plan).
     #[must_use]
     pub fn next_step_with_state<'a>(
         &'a self,
         state: &StateFile,
         desired: StepState,
     ) -> Option<&'a PlanStep> {
-        self.steps
-            .iter()
-            .find(|step| state.state_for(&step.id) == desired)
+        self.steps.iter().find(|step| {
+            state.state_for(&step.id) == desired
+                && step
+                    .depends_on
+                    .iter()
+                    .all(|dep| state.state_for(dep) == StepState::ImplementedCommitted)
+        })
     }
+
+    /// Watch `plan_path` and `state_path` for changes, reloading both and invoking `callback` with
+    /// the freshly resolved next step (matching `desired`) whenever either file settles after an
+    /// edit. Keeps watching until `callback` returns `Ok(false)` or an error.
+    ///
+    /// Both paths are canonicalized once up front, so reloads keep resolving to the same files
+    /// even if the process's working directory changes later. Rapid successive write events (a
+    /// single editor save can fire several) are coalesced by waiting for a quiet period of
+    /// [`WATCH_DEBOUNCE`] before reloading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the filesystem watcher cannot be installed, or if a reload of the plan
+    /// or state file fails.
+    pub fn watch(
+        plan_path: &Path,
+        state_path: &Path,
+        desired: StepState,
+        mut callback: impl FnMut(Option<&PlanStep>) -> Result<bool>,
+    ) -> Result<()> {
+        let plan_path = canonicalize_or_given(plan_path);
+        let state_path = canonicalize_or_given(state_path);
+
+        let (tx, rx) = channel::<notify::Result<Event>>();
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })
+        .context("failed to install filesystem watcher")?;
+        watcher
+            .watch(&plan_path, RecursiveMode::NonRecursive)
+            .with_context(|| format!("failed to watch plan file: {}", plan_path.display()))?;
+        watcher
+            .watch(&state_path, RecursiveMode::NonRecursive)
+            .with_context(|| format!("failed to watch state file: {}", state_path.display()))?;
+
+        loop {
+            if rx.recv().is_err() {
+                return Ok(());
+            }
+            loop {
+                match rx.recv_timeout(WATCH_DEBOUNCE) {
+                    Ok(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            }
+
+            let plan = Self::load(&plan_path)
+                .with_context(|| format!("failed to reload plan file: {}", plan_path.display()))?;
+            let state = StateFile::load(&state_path)
+                .with_context(|| format!("failed to reload state file: {}", state_path.display()))?;
+            let next = plan.next_step_with_state(&state, desired);
+            if !callback(next)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn canonicalize_or_given(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Extract the body of the first ` ```prime ` ... ` ``` ` fenced code block in `contents`, if any.
+fn extract_prime_block(contents: &str) -> Option<&str> {
+    const START_MARKER: &str = "```prime";
+    let start = contents.find(START_MARKER)? + START_MARKER.len();
+    let after_marker = &contents[start..];
+    let rest = after_marker.strip_prefix('\n').unwrap_or(after_marker);
+    let end = rest.find("```")?;
+    Some(&rest[..end])
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    Visiting,
+    Done,
+}
+
+/// Validate that every `depends_on` reference points at a known step and that the dependency
+/// graph has no cycles, via a depth-first walk from each step.
+fn validate_dependency_dag(steps: &[PlanStep]) -> Result<()> {
+    let by_id: HashMap<&str, &PlanStep> = steps.iter().map(|step| (step.id.as_str(), step)).collect();
+    for step in steps {
+        for dep in &step.depends_on {
+            if !by_id.contains_key(dep.as_str()) {
+                return Err(anyhow!(
+                    "step '{}' depends on unknown step '{}'",
+                    step.id,
+                    dep
+                ));
+            }
+        }
+    }
+
+    let mut visited: HashMap<&str, VisitState> = HashMap::new();
+    for step in steps {
+        let mut path = Vec::new();
+        walk_dependencies(&step.id, &by_id, &mut visited, &mut path)?;
+    }
+    Ok(())
+}
+
+fn walk_dependencies<'a>(
+    id: &'a str,
+    by_id: &HashMap<&'a str, &'a PlanStep>,
+    visited: &mut HashMap<&'a str, VisitState>,
+    path: &mut Vec<&'a str>,
+) -> Result<()> {
+    match visited.get(id) {
+        Some(VisitState::Done) => return Ok(()),
+        Some(VisitState::Visiting) => {
+            let cycle_start = path.iter().position(|visited_id| *visited_id == id).unwrap_or(0);
+            let mut cycle: Vec<&str> = path[cycle_start..].to_vec();
+            cycle.push(id);
+            return Err(anyhow!("dependency cycle detected: {}", cycle.join(" -> ")));
+        }
+        None => {}
+    }
+
+    visited.insert(id, VisitState::Visiting);
+    path.push(id);
+    if let Some(step) = by_id.get(id) {
+        for dep in &step.depends_on {
+            walk_dependencies(dep, by_id, visited, path)?;
+        }
+    }
+    path.pop();
+    visited.insert(id, VisitState::Done);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -83,4 +292,52 @@ mod tests {
         assert_eq!(plan.steps[0].text, "First step");
         assert_eq!(plan.steps[1].id, "2");
     }
+
+    #[test]
+    fn parses_structured_prime_block_with_dependencies() {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file");
+        writeln!(
+            file,
+            "# Plan\n\n```prime\n[[steps]]\nid = \"a\"\ntext = \"Set up schema\"\n\n[[steps]]\nid = \"b\"\ntext = \"Write migration\"\ndepends_on = [\"a\"]\nparams = {{ table = \"users\" }}\n```\n"
+        )
+        .expect("write plan");
+        let plan = Plan::load(file.path()).expect("load plan");
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(plan.steps[1].depends_on, vec!["a".to_string()]);
+        assert_eq!(plan.steps[1].params.get("table"), Some(&"users".to_string()));
+    }
+
+    #[test]
+    fn rejects_structured_plan_with_dependency_cycle() {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file");
+        writeln!(
+            file,
+            "```prime\n[[steps]]\nid = \"a\"\ntext = \"A\"\ndepends_on = [\"b\"]\n\n[[steps]]\nid = \"b\"\ntext = \"B\"\ndepends_on = [\"a\"]\n```\n"
+        )
+        .expect("write plan");
+        let err = Plan::load(file.path()).expect_err("cyclic plan should be rejected");
+        assert!(err.to_string().contains("dependency cycle detected"));
+    }
+
+    #[test]
+    fn next_step_with_state_waits_for_incomplete_dependencies() {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file");
+        writeln!(
+            file,
+            "```prime\n[[steps]]\nid = \"a\"\ntext = \"A\"\n\n[[steps]]\nid = \"b\"\ntext = \"B\"\ndepends_on = [\"a\"]\n```\n"
+        )
+        .expect("write plan");
+        let plan = Plan::load(file.path()).expect("load plan");
+        let mut state = StateFile::default();
+        // "a" is mid-flight (not Planned, not committed), "b" defaults to Planned: it should be
+        // skipped until "a" is committed, even though its own state matches `desired`.
+        state.set_state("a", StepState::Implemented);
+        assert!(plan.next_step_with_state(&state, StepState::Planned).is_none());
+
+        state.set_state("a", StepState::ImplementedCommitted);
+        let next = plan
+            .next_step_with_state(&state, StepState::Planned)
+            .expect("step b should now be eligible");
+        assert_eq!(next.id, "b");
+    }
 }
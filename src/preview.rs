@@ -0,0 +1,97 @@
+use anyhow::{bail, Context, Result};
+use std::io::IsTerminal;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
+
+/// Render a skill's markdown body for the terminal: fenced code blocks are syntax-highlighted
+/// with a bundled syntect theme, headings and list items get light emphasis, and everything else
+/// passes through unchanged. Returns `content` verbatim when `color` is `false`.
+///
+/// # Errors
+///
+/// Returns an error if `theme_name` doesn't match a bundled theme, or a code line fails to
+/// highlight.
+pub fn render(content: &str, theme_name: &str, color: bool) -> Result<String> {
+    if !color {
+        return Ok(content.to_string());
+    }
+
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let theme = resolve_theme(theme_name)?;
+
+    let mut output = String::new();
+    let mut highlighter: Option<HighlightLines<'_>> = None;
+
+    for line in content.lines() {
+        if let Some(lang) = line.trim_start().strip_prefix("```") {
+            highlighter = if highlighter.is_some() {
+                None
+            } else {
+                let syntax = syntax_set
+                    .find_syntax_by_token(lang.trim())
+                    .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+                Some(HighlightLines::new(syntax, &theme))
+            };
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        if let Some(highlighter) = highlighter.as_mut() {
+            let ranges = highlighter
+                .highlight_line(line, &syntax_set)
+                .context("failed to highlight code line")?;
+            output.push_str(&as_24_bit_terminal_escaped(&ranges, false));
+            output.push_str("\x1b[0m\n");
+        } else if let Some(heading) = line.strip_prefix("# ") {
+            output.push_str(&format!("\x1b[1;4m{heading}\x1b[0m\n"));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            output.push_str(&format!("\x1b[1m{heading}\x1b[0m\n"));
+        } else if let Some(item) = line.trim_start().strip_prefix("- ") {
+            output.push_str(&format!("  \u{2022} {item}\n"));
+        } else {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+/// Whether `preview` should emit color, given the `--no-color` flag and whether stdout is a TTY.
+#[must_use]
+pub fn should_use_color(no_color: bool) -> bool {
+    !no_color && std::io::stdout().is_terminal()
+}
+
+/// Resolve a bundled theme name (config key `theme`, `"light"` or `"dark"`) to a syntect `Theme`.
+fn resolve_theme(name: &str) -> Result<Theme> {
+    let theme_set = ThemeSet::load_defaults();
+    let key = match name {
+        "light" => "InspiredGitHub",
+        "dark" => "base16-ocean.dark",
+        other => bail!("unknown bundled theme '{other}', expected 'light' or 'dark'"),
+    };
+    theme_set
+        .themes
+        .get(key)
+        .cloned()
+        .with_context(|| format!("unknown bundled theme '{name}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_through_unchanged_without_color() {
+        let content = "# Title\n```rust\nfn main() {}\n```\n";
+        assert_eq!(render(content, "dark", false).expect("render"), content);
+    }
+
+    #[test]
+    fn unrecognized_theme_errors_instead_of_falling_back() {
+        assert!(resolve_theme("nonexistent").is_err());
+    }
+}
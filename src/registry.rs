@@ -0,0 +1,209 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::process::Command;
+
+/// Where a skill source resolves to once the `<registry>/<skill>` shorthand (or a raw URL) has
+/// been parsed against the configured registries.
+enum ResolvedSource {
+    Git { remote: String, skill: String },
+    Url(String),
+}
+
+/// Fetch the markdown body for `source`, resolving it against `registries` first.
+///
+/// `source` is either `<registry>/<skill>` (looked up in `registries`) or a raw `http(s)://` URL.
+/// Git-backed registries are fetched via a shallow clone with sparse-checkout of just the one
+/// skill file; URL sources are fetched with a plain HTTP GET.
+///
+/// # Errors
+///
+/// Returns an error if the source cannot be resolved, or the underlying `git`/HTTP fetch fails.
+pub fn fetch_skill(source: &str, registries: &HashMap<String, String>) -> Result<String> {
+    match resolve_source(source, registries)? {
+        ResolvedSource::Git { remote, skill } => fetch_from_git(&remote, &skill),
+        ResolvedSource::Url(url) => fetch_from_url(&url),
+    }
+}
+
+fn resolve_source(source: &str, registries: &HashMap<String, String>) -> Result<ResolvedSource> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return Ok(ResolvedSource::Url(source.to_string()));
+    }
+
+    let Some((registry_name, skill)) = source.split_once('/') else {
+        bail!("invalid source '{source}', expected '<registry>/<skill>' or a URL");
+    };
+    let Some(base) = registries.get(registry_name) else {
+        bail!("unknown registry '{registry_name}'; configure it under 'registries'");
+    };
+    if base.starts_with("http://") || base.starts_with("https://") {
+        let trimmed_base = base.trim_end_matches('/');
+        return Ok(ResolvedSource::Url(format!("{trimmed_base}/{skill}.md")));
+    }
+    Ok(ResolvedSource::Git {
+        remote: base.clone(),
+        skill: skill.to_string(),
+    })
+}
+
+fn fetch_from_git(remote: &str, skill: &str) -> Result<String> {
+    let checkout = tempfile::tempdir().context("failed to create registry checkout dir")?;
+    let skill_path = format!("{skill}/SKILL.md");
+
+    run_git(&["clone", "--depth", "1", "--filter=blob:none", "--sparse", remote, "."], checkout.path())?;
+    run_git(&["sparse-checkout", "set", &skill_path], checkout.path())?;
+
+    std::fs::read_to_string(checkout.path().join(&skill_path))
+        .with_context(|| format!("skill '{skill}' not found in registry '{remote}'"))
+}
+
+fn run_git(args: &[&str], workdir: &std::path::Path) -> Result<()> {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(workdir)
+        .status()
+        .with_context(|| format!("failed to run git {}", args.join(" ")))?;
+    if !status.success() {
+        bail!("git {} failed with {status}", args.join(" "));
+    }
+    Ok(())
+}
+
+fn fetch_from_url(url: &str) -> Result<String> {
+    let response = reqwest::blocking::get(url).with_context(|| format!("failed to GET '{url}'"))?;
+    if !response.status().is_success() {
+        bail!("GET '{url}' returned {}", response.status());
+    }
+    response.text().with_context(|| format!("failed to read body of '{url}'"))
+}
+
+/// A skill entry as reported by `GET /skills`: its name and a content hash, so callers can tell
+/// whether their local copy is in sync without downloading the body.
+#[derive(Debug, Deserialize)]
+pub struct RegistryEntry {
+    pub name: String,
+    pub hash: String,
+}
+
+/// Talks to a central HTTP skill registry for `sync-remote`: list what it has, fetch a skill
+/// body, or publish a local one. Used in place of [`crate::vcs::GitBackend`] once a
+/// `registry-url` is configured.
+pub struct HttpRegistry {
+    base_url: String,
+    token: Option<String>,
+    client: reqwest::blocking::Client,
+}
+
+impl HttpRegistry {
+    #[must_use]
+    pub fn new(base_url: String, token: Option<String>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: String) -> reqwest::blocking::RequestBuilder {
+        let builder = self.client.request(method, url);
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    /// `GET /skills`: every skill the registry has, with its content hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the server responds with a non-success status, or
+    /// the body isn't a valid JSON listing.
+    pub fn list(&self) -> Result<Vec<RegistryEntry>> {
+        let url = format!("{}/skills", self.base_url);
+        let response =
+            self.request(reqwest::Method::GET, url.clone()).send().with_context(|| format!("failed to GET '{url}'"))?;
+        if !response.status().is_success() {
+            bail!("GET '{url}' returned {}", response.status());
+        }
+        response.json().with_context(|| format!("failed to parse registry listing from '{url}'"))
+    }
+
+    /// `GET /skills/{name}`: the skill's current `SKILL.md` body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the server responds with a non-success status.
+    pub fn fetch(&self, name: &str) -> Result<String> {
+        let url = format!("{}/skills/{name}", self.base_url);
+        let response =
+            self.request(reqwest::Method::GET, url.clone()).send().with_context(|| format!("failed to GET '{url}'"))?;
+        if !response.status().is_success() {
+            bail!("GET '{url}' returned {}", response.status());
+        }
+        response.text().with_context(|| format!("failed to read body of '{url}'"))
+    }
+
+    /// `PUT /skills/{name}`: publish a local skill's body to the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the server responds with a non-success status.
+    pub fn publish(&self, name: &str, content: &str) -> Result<()> {
+        let url = format!("{}/skills/{name}", self.base_url);
+        let response = self
+            .request(reqwest::Method::PUT, url.clone())
+            .body(content.to_string())
+            .send()
+            .with_context(|| format!("failed to PUT '{url}'"))?;
+        if !response.status().is_success() {
+            bail!("PUT '{url}' returned {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+/// SHA-256 of `content`, hex-encoded. The hash format both the registry's `GET /skills` listing
+/// and the local sync-status comparison use.
+#[must_use]
+pub fn content_hash(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    format!("{digest:x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_url_sources_directly() {
+        let registries = HashMap::new();
+        match resolve_source("https://example.com/skill.md", &registries).unwrap() {
+            ResolvedSource::Url(url) => assert_eq!(url, "https://example.com/skill.md"),
+            ResolvedSource::Git { .. } => panic!("expected URL source"),
+        }
+    }
+
+    #[test]
+    fn resolves_registry_shorthand_against_http_base() {
+        let mut registries = HashMap::new();
+        registries.insert("team".to_string(), "https://skills.example.com".to_string());
+        match resolve_source("team/alpha", &registries).unwrap() {
+            ResolvedSource::Url(url) => assert_eq!(url, "https://skills.example.com/alpha.md"),
+            ResolvedSource::Git { .. } => panic!("expected URL source"),
+        }
+    }
+
+    #[test]
+    fn unknown_registry_errors() {
+        let registries = HashMap::new();
+        assert!(resolve_source("team/alpha", &registries).is_err());
+    }
+
+    #[test]
+    fn content_hash_is_stable_and_sensitive_to_content() {
+        assert_eq!(content_hash("same"), content_hash("same"));
+        assert_ne!(content_hash("same"), content_hash("different"));
+    }
+}
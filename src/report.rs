@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// A single compiler diagnostic folded out of a gate's `--message-format=json` output.
+#[derive(Debug, Serialize, Clone)]
+pub struct Diagnostic {
+    pub level: String,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// One gate's outcome within a lifecycle run.
+#[derive(Debug, Serialize)]
+pub struct GateRecord {
+    pub name: String,
+    pub command_line: String,
+    pub success: bool,
+    pub duration_ms: u128,
+    pub stdout_tail: Vec<String>,
+    pub stderr_tail: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+    /// The gate process's raw exit code, when it ran to completion (`None` if it couldn't be
+    /// spawned at all). The stored `StepState` for a failed gate is `LifecycleError` of the
+    /// running stage whatever this code is.
+    pub exit_code: Option<i32>,
+    /// Whether this gate's exit code matched its configured `unstable_exit_code` rather than
+    /// passing cleanly or hard-failing; `success` is still `true` for these, but the lifecycle
+    /// pauses for human review instead of advancing.
+    pub unstable: bool,
+}
+
+/// One lifecycle-stage execution within a run.
+#[derive(Debug, Serialize)]
+pub struct StepRecord {
+    pub step_id: String,
+    pub step_number: usize,
+    pub lifecycle: u8,
+    pub action: String,
+    pub success: bool,
+    pub duration_ms: u128,
+    pub gates: Vec<GateRecord>,
+}
+
+/// Accumulates structured step/gate records for a lifecycle run and serializes them to JSON for
+/// CI systems or dashboards, rather than leaving results only in the human-readable log.
+#[derive(Debug, Default, Serialize)]
+pub struct RunReport {
+    pub steps: Vec<StepRecord>,
+}
+
+impl RunReport {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_step(&mut self, record: StepRecord) {
+        self.steps.push(record);
+    }
+
+    /// Serialize the accumulated report as pretty JSON to `path`, creating parent directories as
+    /// needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be serialized or written.
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create report dir: {}", parent.display()))?;
+        }
+        let contents = serde_json::to_string_pretty(self).context("failed to serialize run report")?;
+        std::fs::write(path, contents).with_context(|| format!("failed to write run report: {}", path.display()))
+    }
+}
+
+/// Parse `cargo ... --message-format=json` stdout into diagnostics (level/message/file/line),
+/// keeping only `"reason": "compiler-message"` entries and each message's primary span.
+#[must_use]
+pub fn parse_cargo_diagnostics(stdout: &str) -> Vec<Diagnostic> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|value| value.get("reason").and_then(serde_json::Value::as_str) == Some("compiler-message"))
+        .filter_map(|value| {
+            let message = value.get("message")?;
+            let level = message.get("level")?.as_str()?.to_string();
+            let text = message.get("message")?.as_str()?.to_string();
+            let primary_span = message
+                .get("spans")?
+                .as_array()?
+                .iter()
+                .find(|span| span.get("is_primary").and_then(serde_json::Value::as_bool) == Some(true));
+            let file = primary_span
+                .and_then(|span| span.get("file_name"))
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string);
+            let line = primary_span
+                .and_then(|span| span.get("line_start"))
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|value| u32::try_from(value).ok());
+            Some(Diagnostic { level, message: text, file, line })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compiler_message_with_primary_span() {
+        let stdout = r#"{"reason":"compiler-artifact"}
+{"reason":"compiler-message","message":{"level":"warning","message":"unused variable","spans":[{"is_primary":true,"file_name":"src/main.rs","line_start":12}]}}"#;
+        let diagnostics = parse_cargo_diagnostics(stdout);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, "warning");
+        assert_eq!(diagnostics[0].file.as_deref(), Some("src/main.rs"));
+        assert_eq!(diagnostics[0].line, Some(12));
+    }
+}
@@ -1,8 +1,10 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 /// Lifecycle state for a plan step.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +16,9 @@ pub enum StepState {
     ImplementedFinalized,
     ImplementedCommitted,
     LifecycleError(u8),
+    /// A gate hit its configured `unstable_exit_code` during this stage: not a hard failure, but
+    /// the lifecycle stops here for a human to look at before continuing.
+    Paused(u8),
 }
 
 impl StepState {
@@ -28,6 +33,7 @@ impl StepState {
             StepState::ImplementedFinalized => "implemented-finalized".to_string(),
             StepState::ImplementedCommitted => "implemented-committed".to_string(),
             StepState::LifecycleError(stage) => format!("lifecycle-error-{stage}"),
+            StepState::Paused(stage) => format!("paused-{stage}"),
         }
     }
 
@@ -35,6 +41,29 @@ impl StepState {
     pub fn lifecycle_error(stage: u8) -> Self {
         Self::LifecycleError(stage)
     }
+
+    /// Translate a stage number (1-5, matching [`crate::lifecycle::run_lifecycle`]'s `lifecycle`
+    /// argument) and the exit code of the command that ran it directly into the resulting state:
+    /// the stage's success state when `exit_code == 0`, or `LifecycleError(stage)` otherwise.
+    #[must_use]
+    pub fn from_stage_result(stage: u8, exit_code: i32) -> Self {
+        if exit_code == 0 {
+            Self::success_state_for_stage(stage)
+        } else {
+            Self::LifecycleError(stage)
+        }
+    }
+
+    fn success_state_for_stage(stage: u8) -> Self {
+        match stage {
+            1 => Self::Implemented,
+            2 => Self::ImplementedChecked,
+            3 => Self::ImplementedTested,
+            4 => Self::ImplementedFinalized,
+            5 => Self::ImplementedCommitted,
+            _ => Self::LifecycleError(stage),
+        }
+    }
 }
 
 impl Serialize for StepState {
@@ -66,71 +95,277 @@ impl<'de> Deserialize<'de> for StepState {
                         .map_err(|_| de::Error::custom("invalid lifecycle error stage"))?;
                     return Ok(StepState::LifecycleError(parsed));
                 }
+                if let Some(stage) = value.strip_prefix("paused-") {
+                    let parsed = stage
+                        .parse::<u8>()
+                        .map_err(|_| de::Error::custom("invalid paused stage"))?;
+                    return Ok(StepState::Paused(parsed));
+                }
                 Err(de::Error::custom("unknown step state"))
             }
         }
     }
 }
 
+/// Current on-disk schema version for [`StateFile`]. Bump this and add a `migrate_vN_to_vN1` entry
+/// to [`MIGRATIONS`] whenever the shape of `StateFile` or its step values changes.
+const CURRENT_STATE_VERSION: u32 = 2;
+
+/// Ordered `from_version -> migration` chain, applied in order starting from whatever version an
+/// on-disk file was last saved with, up to [`CURRENT_STATE_VERSION`].
+const MIGRATIONS: &[(u32, fn(serde_json::Value) -> Result<serde_json::Value>)] = &[(1, migrate_v1_to_v2)];
+
+fn default_state_version() -> u32 {
+    1
+}
+
+/// A single recorded state change for a step, kept for audit/rollback purposes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transition {
+    pub state: StepState,
+    /// Nanoseconds since the first transition this process recorded; only meaningful for ordering transitions recorded
+    /// within the same run (a monotonic clock has no fixed reference across process restarts).
+    pub at_monotonic: u128,
+    /// Milliseconds since the Unix epoch, for human-facing and cross-run history.
+    pub at_utc: u128,
+}
+
+impl Transition {
+    fn now(state: StepState) -> Self {
+        Self {
+            state,
+            at_monotonic: process_clock().elapsed().as_nanos(),
+            at_utc: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis(),
+        }
+    }
+}
+
+fn process_clock() -> &'static Instant {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now)
+}
+
+/// A step's current state plus the ordered history of transitions that led there.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub current: StepState,
+    #[serde(default)]
+    pub history: Vec<Transition>,
+}
+
+impl StepRecord {
+    fn new(state: StepState) -> Self {
+        Self {
+            current: state,
+            history: vec![Transition::now(state)],
+        }
+    }
+
+    fn push(&mut self, state: StepState) {
+        self.current = state;
+        self.history.push(Transition::now(state));
+    }
+}
+
 /// State file that tracks each step's lifecycle.
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct StateFile {
+    #[serde(default = "default_state_version")]
+    pub version: u32,
     #[serde(default)]
-    pub steps: HashMap<String, StepState>,
+    pub steps: HashMap<String, StepRecord>,
+}
+
+impl Default for StateFile {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_STATE_VERSION,
+            steps: HashMap::new(),
+        }
+    }
 }
 
 impl StateFile {
-    /// Load state from JSON or return an empty state if missing.
+    /// Load state from JSON, migrating it forward from whatever version it was saved with, or
+    /// return an empty state if the file is missing.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file exists but cannot be read or parsed.
+    /// Returns an error if the file exists but cannot be read, parsed, or migrated.
     pub fn load(path: &Path) -> Result<Self> {
         if !path.exists() {
             return Ok(Self::default());
         }
         let contents = fs::read_to_string(path)
             .with_context(|| format!("failed to read state file: {}", path.display()))?;
-        let parsed: Self = serde_json::from_str(&contents)
+        let raw: serde_json::Value = serde_json::from_str(&contents)
             .with_context(|| format!("failed to parse state JSON: {}", path.display()))?;
+        let migrated = migrate_to_current(raw)
+            .with_context(|| format!("failed to migrate state file: {}", path.display()))?;
+        let parsed: Self = serde_json::from_value(migrated)
+            .with_context(|| format!("failed to parse migrated state: {}", path.display()))?;
         Ok(parsed)
     }
 
-    /// Persist the state to disk.
+    /// Persist the state to disk, always stamping the current schema version regardless of what
+    /// `self.version` happened to hold. Rotates whatever was previously at `path` to a sibling
+    /// `*.previous` file first, then writes the new content to a temp file and renames it into
+    /// place so a crash mid-write can never leave a half-written state file.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written.
+    /// Returns an error if the previous file can't be rotated or the new file can't be written.
     pub fn save(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("failed to create state dir: {}", parent.display()))?;
         }
-        let contents = serde_json::to_string_pretty(self).context("failed to serialize state")?;
-        fs::write(path, contents)
-            .with_context(|| format!("failed to write state file: {}", path.display()))?;
+        let mut value = serde_json::to_value(self).context("failed to serialize state")?;
+        if let serde_json::Value::Object(map) = &mut value {
+            map.insert("version".to_string(), serde_json::json!(CURRENT_STATE_VERSION));
+        }
+        let contents = serde_json::to_string_pretty(&value).context("failed to serialize state")?;
+
+        if path.exists() {
+            let previous_path = previous_path_for(path);
+            fs::copy(path, &previous_path)
+                .with_context(|| format!("failed to rotate previous state to '{}'", previous_path.display()))?;
+        }
+
+        let tmp_path = tmp_path_for(path);
+        fs::write(&tmp_path, &contents)
+            .with_context(|| format!("failed to write state temp file: {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("failed to install state file: {}", path.display()))?;
         Ok(())
     }
 
     /// Get the state for a step, defaulting to planned.
     #[must_use]
     pub fn state_for(&self, step_id: &str) -> StepState {
-        self.steps
-            .get(step_id)
-            .copied()
-            .unwrap_or(StepState::Planned)
+        self.steps.get(step_id).map_or(StepState::Planned, |record| record.current)
     }
 
-    /// Update the state for a step.
+    /// Update the state for a step, appending a timestamped entry to its transition history.
     pub fn set_state(&mut self, step_id: &str, state: StepState) {
-        self.steps.insert(step_id.to_string(), state);
+        self.steps
+            .entry(step_id.to_string())
+            .and_modify(|record| record.push(state))
+            .or_insert_with(|| StepRecord::new(state));
+    }
+
+    /// The ordered transition history recorded for a step, oldest first. Empty if the step has no
+    /// recorded state yet.
+    #[must_use]
+    pub fn history(&self, step_id: &str) -> &[Transition] {
+        self.steps.get(step_id).map_or(&[][..], |record| record.history.as_slice())
+    }
+
+    /// Restore a step to its last-but-one recorded state, discarding the most recent transition.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the step has no recorded history, or has only a single transition (so
+    /// there is nothing to roll back to).
+    pub fn rollback(&mut self, step_id: &str) -> Result<()> {
+        let record = self
+            .steps
+            .get_mut(step_id)
+            .with_context(|| format!("no recorded state for step '{step_id}'"))?;
+        if record.history.len() < 2 {
+            bail!("step '{step_id}' has no prior state to roll back to");
+        }
+        record.history.pop();
+        record.current = record.history.last().expect("checked len >= 2 above").state;
+        Ok(())
+    }
+}
+
+/// The sibling path used to rotate a state file's previous contents, e.g. `state.current.json` ->
+/// `state.previous.json`, or `state.json` -> `state.json.previous` for arbitrary paths.
+fn previous_path_for(path: &Path) -> PathBuf {
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("state.json");
+    let previous_name = if file_name.contains("current") {
+        file_name.replacen("current", "previous", 1)
+    } else {
+        format!("{file_name}.previous")
+    };
+    path.with_file_name(previous_name)
+}
+
+fn tmp_path_for(path: &Path) -> PathBuf {
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("state.json");
+    path.with_file_name(format!("{file_name}.tmp"))
+}
+
+/// v1 stored each step as a bare `StepState` string; v2 wraps it in a [`StepRecord`] with history.
+/// Migrated entries get a single synthetic transition (timestamps unknown, so zeroed) so the
+/// invariant "every recorded step has at least one history entry" still holds.
+fn migrate_v1_to_v2(mut value: serde_json::Value) -> Result<serde_json::Value> {
+    if let Some(serde_json::Value::Object(steps)) = value.get_mut("steps") {
+        for step_value in steps.values_mut() {
+            if step_value.is_string() {
+                let state = step_value.clone();
+                *step_value = serde_json::json!({
+                    "current": state,
+                    "history": [{ "state": state, "at_monotonic": 0, "at_utc": 0 }],
+                });
+            }
+        }
     }
+    Ok(value)
+}
+
+/// Run every migration in [`MIGRATIONS`] whose `from_version` is at or after the file's recorded
+/// version (defaulting to 1 for files predating the `version` field), then stamp the result with
+/// [`CURRENT_STATE_VERSION`].
+fn migrate_to_current(mut value: serde_json::Value) -> Result<serde_json::Value> {
+    let from_version = value.get("version").and_then(serde_json::Value::as_u64).unwrap_or(1) as u32;
+    for (applies_from, migrate) in MIGRATIONS {
+        if from_version <= *applies_from {
+            value = migrate(value)?;
+        }
+    }
+    if let serde_json::Value::Object(map) = &mut value {
+        map.insert("version".to_string(), serde_json::json!(CURRENT_STATE_VERSION));
+    }
+    Ok(value)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn loads_legacy_v1_file_and_migrates_bare_states() {
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        fs::write(file.path(), r#"{"version": 1, "steps": {"1": "implemented"}}"#).expect("write legacy state");
+        let state = StateFile::load(file.path()).expect("load state");
+        assert_eq!(state.version, CURRENT_STATE_VERSION);
+        assert_eq!(state.state_for("1"), StepState::Implemented);
+        assert_eq!(state.history("1").len(), 1);
+    }
+
+    #[test]
+    fn loads_legacy_file_without_version_field() {
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        fs::write(file.path(), r#"{"steps": {"1": "implemented"}}"#).expect("write legacy state");
+        let state = StateFile::load(file.path()).expect("load state");
+        assert_eq!(state.version, CURRENT_STATE_VERSION);
+        assert_eq!(state.state_for("1"), StepState::Implemented);
+    }
+
+    #[test]
+    fn save_always_stamps_current_version() {
+        let mut state = StateFile::default();
+        state.set_state("1", StepState::Implemented);
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        state.save(file.path()).expect("save state");
+        let raw: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(file.path()).expect("read back")).expect("parse");
+        assert_eq!(raw["version"], CURRENT_STATE_VERSION);
+    }
+
     #[test]
     fn saves_and_loads_state() {
         let mut state = StateFile::default();
@@ -142,6 +377,51 @@ mod tests {
         assert_eq!(loaded.state_for("2"), StepState::Planned);
     }
 
+    #[test]
+    fn save_rotates_previous_file() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("state.current.json");
+
+        let mut state = StateFile::default();
+        state.set_state("1", StepState::Planned);
+        state.save(&path).expect("save first version");
+
+        state.set_state("1", StepState::Implemented);
+        state.save(&path).expect("save second version");
+
+        let previous = dir.path().join("state.previous.json");
+        assert!(previous.exists());
+        let previous_state = StateFile::load(&previous).expect("load previous state");
+        assert_eq!(previous_state.state_for("1"), StepState::Planned);
+    }
+
+    #[test]
+    fn history_accumulates_and_rollback_restores_prior_state() {
+        let mut state = StateFile::default();
+        state.set_state("1", StepState::Planned);
+        state.set_state("1", StepState::Implemented);
+        state.set_state("1", StepState::ImplementedChecked);
+        assert_eq!(state.history("1").len(), 3);
+
+        state.rollback("1").expect("rollback");
+        assert_eq!(state.state_for("1"), StepState::Implemented);
+        assert_eq!(state.history("1").len(), 2);
+    }
+
+    #[test]
+    fn rollback_fails_with_no_prior_state() {
+        let mut state = StateFile::default();
+        state.set_state("1", StepState::Planned);
+        assert!(state.rollback("1").is_err());
+    }
+
+    #[test]
+    fn from_stage_result_maps_exit_code_to_success_or_error_state() {
+        assert_eq!(StepState::from_stage_result(1, 0), StepState::Implemented);
+        assert_eq!(StepState::from_stage_result(3, 0), StepState::ImplementedTested);
+        assert_eq!(StepState::from_stage_result(3, 1), StepState::LifecycleError(3));
+    }
+
     #[test]
     fn serializes_error_state() {
         let state = StepState::LifecycleError(3);
@@ -150,4 +430,13 @@ mod tests {
         let parsed: StepState = serde_json::from_str(&json).expect("deserialize");
         assert_eq!(parsed, StepState::LifecycleError(3));
     }
+
+    #[test]
+    fn serializes_paused_state() {
+        let state = StepState::Paused(2);
+        let json = serde_json::to_string(&state).expect("serialize");
+        assert_eq!(json, "\"paused-2\"");
+        let parsed: StepState = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, StepState::Paused(2));
+    }
 }
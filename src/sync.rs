@@ -1,16 +1,20 @@
 use crate::agents_md::{AgentSection, AgentsDoc};
+use crate::registry::{self, HttpRegistry};
 use crate::skills_store::SkillsStore;
+use crate::vcs::{self, VcsBackend};
 use anyhow::{bail, Context, Result};
-use similar::{ChangeTag, TextDiff};
+use similar::{ChangeTag, DiffOp, TextDiff};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io::{self, Write};
+use std::ops::Range;
 use std::path::Path;
 use std::process::Command;
 
 pub fn run_sync(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
+    let backend = vcs::backend_from_env();
     if !agents_path.exists() {
-        commit_skills_repo(skills_store.root())?;
+        commit_skills_repo(backend.as_ref(), skills_store.root())?;
         return Ok(());
     }
     let (mut agents_doc, original_agents) = read_agents_doc(agents_path)?;
@@ -34,7 +38,11 @@ pub fn run_sync(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
                 let skill_content = skills_store.load_skill(&name)?;
                 let agents_content = section.content_string();
                 if normalize_content(&skill_content) != normalize_content(&agents_content) {
-                    let resolved = resolve_conflicts_interactive(&name, &skill_content, &agents_content)?;
+                    let skill_path = skills_store.root().join(&name).join("SKILL.md");
+                    let ancestor =
+                        backend.last_committed_content(skills_store.root(), &skill_path)?;
+                    let resolved =
+                        resolve_conflicts(&name, ancestor.as_deref(), &skill_content, &agents_content, "skill", "agents")?;
                     skills_store.save_skill(&name, &resolved)?;
                     agents_doc.upsert_section(AgentSection::from_content(name, &resolved));
                     updated = true;
@@ -50,16 +58,110 @@ pub fn run_sync(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
             .with_context(|| format!("failed to write '{}'", agents_path.display()))?;
     }
 
-    commit_skills_repo(skills_store.root())?;
+    commit_skills_repo(backend.as_ref(), skills_store.root())?;
     Ok(())
 }
 
-pub fn run_sync_remote(skills_store: &SkillsStore, agents_path: &Path) -> Result<()> {
+/// Sync skills with `AGENTS.md`, then reconcile with the remote: an HTTP registry if
+/// `registry_url` is set, otherwise the git remote (`git pull --rebase`, as before).
+pub fn run_sync_remote(
+    skills_store: &SkillsStore,
+    agents_path: &Path,
+    registry_url: Option<&str>,
+    registry_token: Option<&str>,
+) -> Result<()> {
     run_sync(skills_store, agents_path)?;
-    git_pull_rebase(skills_store.root())?;
+    match registry_url {
+        Some(base_url) => sync_via_registry(skills_store, base_url, registry_token),
+        None => {
+            let backend = vcs::backend_from_env();
+            if backend.is_repo(skills_store.root())? {
+                backend.pull(skills_store.root())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Reconcile the local skill store against an HTTP registry: publish anything local that's new,
+/// pull down anything the registry has that we don't, and three-way merge anything that changed on
+/// both sides (same `diff3_merge` machinery `run_sync` uses for the AGENTS.md side), by comparing
+/// content hashes.
+fn sync_via_registry(skills_store: &SkillsStore, base_url: &str, token: Option<&str>) -> Result<()> {
+    let registry = HttpRegistry::new(base_url.to_string(), token.map(str::to_string));
+    let backend = vcs::backend_from_env();
+    let mut remote: HashMap<String, String> =
+        registry.list()?.into_iter().map(|entry| (entry.name, entry.hash)).collect();
+
+    for name in skills_store.list_skill_names()? {
+        let content = skills_store.load_skill(&name)?;
+        let local_hash = registry::content_hash(&content);
+        match remote.remove(&name) {
+            Some(remote_hash) if remote_hash == local_hash => {}
+            Some(_) => {
+                let remote_content = registry.fetch(&name)?;
+                let skill_path = skills_store.root().join(&name).join("SKILL.md");
+                let ancestor = backend.last_committed_content(skills_store.root(), &skill_path)?;
+                let resolved =
+                    resolve_conflicts(&name, ancestor.as_deref(), &content, &remote_content, "skill", "registry")?;
+                skills_store.save_skill(&name, &resolved)?;
+                registry.publish(&name, &resolved)?;
+            }
+            None => registry.publish(&name, &content)?,
+        }
+    }
+
+    for name in remote.into_keys() {
+        let content = registry.fetch(&name)?;
+        skills_store.save_skill(&name, &content)?;
+    }
     Ok(())
 }
 
+/// How a local skill compares to its entry in an HTTP registry, by content hash alone (no
+/// timestamps or versions to establish a true "newer"/"older" ordering).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegistryStatus {
+    InSync,
+    /// Exists locally but not (yet) in the registry.
+    Ahead,
+    /// Exists in the registry but not locally.
+    Behind,
+    /// Exists on both sides with different content.
+    Conflict,
+}
+
+/// Compare every local skill's content hash against the registry's `GET /skills` listing.
+///
+/// # Errors
+///
+/// Returns an error if a skill can't be loaded or the registry request fails.
+pub fn compute_registry_status(
+    skills_store: &SkillsStore,
+    registry_url: &str,
+    registry_token: Option<&str>,
+) -> Result<BTreeMap<String, RegistryStatus>> {
+    let registry = HttpRegistry::new(registry_url.to_string(), registry_token.map(str::to_string));
+    let mut remote: HashMap<String, String> =
+        registry.list()?.into_iter().map(|entry| (entry.name, entry.hash)).collect();
+
+    let mut statuses = BTreeMap::new();
+    for name in skills_store.list_skill_names()? {
+        let content = skills_store.load_skill(&name)?;
+        let local_hash = registry::content_hash(&content);
+        let status = match remote.remove(&name) {
+            Some(remote_hash) if remote_hash == local_hash => RegistryStatus::InSync,
+            Some(_) => RegistryStatus::Conflict,
+            None => RegistryStatus::Ahead,
+        };
+        statuses.insert(name, status);
+    }
+    for name in remote.into_keys() {
+        statuses.insert(name, RegistryStatus::Behind);
+    }
+    Ok(statuses)
+}
+
 fn read_agents_doc(path: &Path) -> Result<(AgentsDoc, Option<String>)> {
     if path.exists() {
         let contents = fs::read_to_string(path)
@@ -71,33 +173,209 @@ fn read_agents_doc(path: &Path) -> Result<(AgentsDoc, Option<String>)> {
     }
 }
 
+/// Resolve an out-of-sync skill. When `ancestor` (the git-committed copy of `SKILL.md`) is
+/// available, attempt a line-level three-way merge; spans both sides changed differently are left
+/// in the returned text as conflict-marker blocks for manual resolution, with a notice printed.
+/// With no ancestor at all (e.g. the skill was never committed) the pick prompt is all we can do.
+/// `left_label`/`right_label` name the two sides of the conflict (e.g. `"skill"`/`"agents"`, or
+/// `"skill"`/`"registry"`) for the interactive prompt and conflict markers.
+fn resolve_conflicts(
+    name: &str,
+    ancestor: Option<&str>,
+    left_content: &str,
+    right_content: &str,
+    left_label: &str,
+    right_label: &str,
+) -> Result<String> {
+    let Some(ancestor) = ancestor else {
+        return resolve_conflicts_interactive(name, left_content, right_content, left_label, right_label);
+    };
+    let (merged, conflicted) = diff3_merge(ancestor, left_content, right_content, left_label, right_label);
+    if conflicted {
+        println!(
+            "\nSkill '{name}' has conflicting changes on both sides; conflict markers were left in place for manual resolution."
+        );
+    }
+    Ok(merged)
+}
+
+/// A contiguous span of `ancestor` lines (indices into its line-split form) where one side of a
+/// diff changed something, paired with the replacement lines that side put there instead.
+struct ChangeRegion {
+    range: Range<usize>,
+    lines: Vec<String>,
+}
+
+/// Extract the non-`Equal` ops of an ancestor-vs-side diff as ancestor-anchored change regions.
+fn change_regions(diff: &TextDiff<'_, '_, '_, str>) -> Vec<ChangeRegion> {
+    diff.ops()
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Equal { .. }))
+        .map(|op| {
+            let lines = diff
+                .iter_changes(op)
+                .filter(|change| change.tag() == ChangeTag::Insert)
+                .map(|change| change.value().to_string())
+                .collect();
+            ChangeRegion { range: op.old_range(), lines }
+        })
+        .collect()
+}
+
+/// A change region after merging the skill- and agents-side edits that overlap or touch each
+/// other in ancestor-line space; `None` on a side means that side left this span untouched.
+struct MergedRegion {
+    range: Range<usize>,
+    skill_lines: Option<Vec<String>>,
+    agents_lines: Option<Vec<String>>,
+}
+
+/// Sweep two sides' change regions (each already sorted by ancestor line index) into merged
+/// groups, so overlapping or adjacent edits from both sides are judged together.
+fn merge_change_regions(skill: Vec<ChangeRegion>, agents: Vec<ChangeRegion>) -> Vec<MergedRegion> {
+    let mut skill = skill.into_iter().peekable();
+    let mut agents = agents.into_iter().peekable();
+    let mut merged = Vec::new();
+
+    loop {
+        let start = match (skill.peek(), agents.peek()) {
+            (None, None) => break,
+            (Some(s), None) => s.range.start,
+            (None, Some(a)) => a.range.start,
+            (Some(s), Some(a)) => s.range.start.min(a.range.start),
+        };
+        let mut end = start;
+        let mut skill_lines = None;
+        let mut agents_lines = None;
+
+        loop {
+            let mut advanced = false;
+            if skill.peek().is_some_and(|region| region.range.start <= end) {
+                let region = skill.next().expect("peeked Some above");
+                end = end.max(region.range.end);
+                skill_lines.get_or_insert_with(Vec::new).extend(region.lines);
+                advanced = true;
+            }
+            if agents.peek().is_some_and(|region| region.range.start <= end) {
+                let region = agents.next().expect("peeked Some above");
+                end = end.max(region.range.end);
+                agents_lines.get_or_insert_with(Vec::new).extend(region.lines);
+                advanced = true;
+            }
+            if !advanced {
+                break;
+            }
+        }
+
+        merged.push(MergedRegion { range: start..end, skill_lines, agents_lines });
+    }
+    merged
+}
+
+/// Merge `skill` and `agents`, both derived from `ancestor`, line by line: spans only one side
+/// touched are taken as-is, spans both sides changed identically collapse to that text, and spans
+/// the two sides changed differently become a `<<<<<<<`/`=======`/`>>>>>>>` conflict block.
+/// Returns the merged text and whether it contains any conflict markers. A conflict-free merge
+/// round-trips the ancestor's trailing-newline state byte-for-byte.
+fn diff3_merge(
+    ancestor: &str,
+    skill: &str,
+    agents: &str,
+    skill_label: &str,
+    agents_label: &str,
+) -> (String, bool) {
+    let skill_diff = TextDiff::from_lines(ancestor, skill);
+    let agents_diff = TextDiff::from_lines(ancestor, agents);
+    let merged_regions =
+        merge_change_regions(change_regions(&skill_diff), change_regions(&agents_diff));
+
+    let ancestor_lines: Vec<&str> = ancestor.split_inclusive('\n').collect();
+    let mut out = String::new();
+    let mut conflicted = false;
+    let mut cursor = 0;
+
+    for region in merged_regions {
+        for line in &ancestor_lines[cursor..region.range.start] {
+            out.push_str(line);
+        }
+        match (region.skill_lines, region.agents_lines) {
+            (Some(lines), None) | (None, Some(lines)) => {
+                for line in &lines {
+                    out.push_str(line);
+                }
+            }
+            (Some(skill_lines), Some(agents_lines)) => {
+                if skill_lines == agents_lines {
+                    for line in &skill_lines {
+                        out.push_str(line);
+                    }
+                } else {
+                    conflicted = true;
+                    out.push_str(&format!("<<<<<<< {skill_label}\n"));
+                    push_conflict_lines(&mut out, &skill_lines);
+                    out.push_str("=======\n");
+                    push_conflict_lines(&mut out, &agents_lines);
+                    out.push_str(&format!(">>>>>>> {agents_label}\n"));
+                }
+            }
+            (None, None) => unreachable!("a merged region always has at least one side's edit"),
+        }
+        cursor = region.range.end;
+    }
+    for line in &ancestor_lines[cursor..] {
+        out.push_str(line);
+    }
+
+    (out, conflicted)
+}
+
+/// Append `lines` inside a conflict block, making sure each one ends its own line even if the
+/// original content didn't, so the closing marker never lands glued onto the last line.
+fn push_conflict_lines(out: &mut String, lines: &[String]) {
+    for line in lines {
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+}
+
 fn resolve_conflicts_interactive(
     name: &str,
-    skill_content: &str,
-    agents_content: &str,
+    left_content: &str,
+    right_content: &str,
+    left_label: &str,
+    right_label: &str,
 ) -> Result<String> {
-    let diff = TextDiff::from_lines(skill_content, agents_content);
+    let diff = TextDiff::from_lines(left_content, right_content);
     if diff.ops().is_empty() {
-        return Ok(skill_content.to_string());
+        return Ok(left_content.to_string());
     }
 
     let mut resolved = String::new();
     for group in diff.grouped_ops(3) {
-        let hunk = render_hunk(&diff, &group);
+        let hunk = render_unified_hunk(&diff, &group);
         println!("\nConflict in skill '{name}':\n{hunk}");
-        let choice = prompt_choice()?;
-        for op in &group {
-            for change in diff.iter_changes(op) {
-                match change.tag() {
-                    ChangeTag::Equal => resolved.push_str(change.value()),
-                    ChangeTag::Delete => {
-                        if choice == Choice::Skill {
-                            resolved.push_str(change.value());
-                        }
-                    }
-                    ChangeTag::Insert => {
-                        if choice == Choice::Agents {
-                            resolved.push_str(change.value());
+        let choice = prompt_choice(left_label, right_label)?;
+        match choice {
+            Choice::Edit => {
+                resolved.push_str(&resolve_hunk_in_editor(&diff, &group, &hunk, left_label, right_label)?);
+            }
+            Choice::Left | Choice::Right | Choice::Both => {
+                for op in &group {
+                    for change in diff.iter_changes(op) {
+                        match change.tag() {
+                            ChangeTag::Equal => resolved.push_str(change.value()),
+                            ChangeTag::Delete => {
+                                if choice == Choice::Left || choice == Choice::Both {
+                                    resolved.push_str(change.value());
+                                }
+                            }
+                            ChangeTag::Insert => {
+                                if choice == Choice::Right || choice == Choice::Both {
+                                    resolved.push_str(change.value());
+                                }
+                            }
                         }
                     }
                 }
@@ -108,8 +386,27 @@ fn resolve_conflicts_interactive(
     Ok(resolved)
 }
 
-fn render_hunk(diff: &TextDiff<'_, '_, '_, str>, group: &[similar::DiffOp]) -> String {
-    let mut out = String::new();
+/// Render a diff group as a standard unified-diff hunk (`@@ -a,b +c,d @@` plus
+/// context/`-`/`+` lines), so the output is copy-pasteable into other tools.
+fn render_unified_hunk(diff: &TextDiff<'_, '_, '_, str>, group: &[DiffOp]) -> String {
+    let mut old_count = 0usize;
+    let mut new_count = 0usize;
+    for op in group {
+        for change in diff.iter_changes(op) {
+            match change.tag() {
+                ChangeTag::Equal => {
+                    old_count += 1;
+                    new_count += 1;
+                }
+                ChangeTag::Delete => old_count += 1,
+                ChangeTag::Insert => new_count += 1,
+            }
+        }
+    }
+    let old_start = group.first().map_or(0, |op| op.old_range().start);
+    let new_start = group.first().map_or(0, |op| op.new_range().start);
+
+    let mut out = format_hunk_header(old_start, old_count, new_start, new_count);
     for op in group {
         for change in diff.iter_changes(op) {
             let sign = match change.tag() {
@@ -119,37 +416,157 @@ fn render_hunk(diff: &TextDiff<'_, '_, '_, str>, group: &[similar::DiffOp]) -> S
             };
             out.push_str(sign);
             out.push_str(change.value());
+            if !change.value().ends_with('\n') {
+                out.push('\n');
+            }
         }
     }
     out
 }
 
+fn format_hunk_header(old_start: usize, old_count: usize, new_start: usize, new_count: usize) -> String {
+    let old_part = format_hunk_range(old_start, old_count);
+    let new_part = format_hunk_range(new_start, new_count);
+    format!("@@ -{old_part} +{new_part} @@\n")
+}
+
+fn format_hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        return "0,0".to_string();
+    }
+    let one_based_start = start + 1;
+    if count == 1 {
+        one_based_start.to_string()
+    } else {
+        format!("{one_based_start},{count}")
+    }
+}
+
+fn resolve_hunk_in_editor(
+    diff: &TextDiff<'_, '_, '_, str>,
+    group: &[DiffOp],
+    hunk: &str,
+    left_label: &str,
+    right_label: &str,
+) -> Result<String> {
+    let mut left_side = String::new();
+    let mut right_side = String::new();
+    for op in group {
+        for change in diff.iter_changes(op) {
+            match change.tag() {
+                ChangeTag::Equal => {
+                    left_side.push_str(change.value());
+                    right_side.push_str(change.value());
+                }
+                ChangeTag::Delete => left_side.push_str(change.value()),
+                ChangeTag::Insert => right_side.push_str(change.value()),
+            }
+        }
+    }
+
+    let mut scratch = tempfile::NamedTempFile::new().context("failed to create merge scratch file")?;
+    write!(
+        scratch,
+        "<<<<<<< {left_label}\n{left_side}=======\n{right_side}>>>>>>> {right_label}\n\n# Hunk for reference:\n"
+    )
+    .context("failed to write merge scratch file")?;
+    for line in hunk.lines() {
+        writeln!(scratch, "# {line}").context("failed to write merge scratch file")?;
+    }
+    scratch.flush().context("failed to flush merge scratch file")?;
+
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    let status = Command::new(&editor)
+        .arg(scratch.path())
+        .status()
+        .with_context(|| format!("failed to launch editor '{editor}'"))?;
+    if !status.success() {
+        bail!("editor '{editor}' exited with {status}");
+    }
+
+    let edited = fs::read_to_string(scratch.path()).context("failed to read merge scratch file")?;
+    let resolved = edited
+        .lines()
+        .take_while(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(resolved.trim_end_matches('\n').to_string() + "\n")
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Choice {
-    Skill,
-    Agents,
+    Left,
+    Right,
+    Both,
+    Edit,
+}
+
+/// Split `label` into its lowercased first character (the prompt shortcut key) and the rest,
+/// e.g. `"registry"` -> `('r', "egistry")`, so the prompt reads `[r]egistry` the way a hardcoded
+/// `[a]gents` used to.
+fn prompt_key(label: &str) -> (char, &str) {
+    let mut chars = label.chars();
+    let key = chars.next().map_or(' ', |ch| ch.to_ascii_lowercase());
+    (key, chars.as_str())
 }
 
-fn prompt_choice() -> Result<Choice> {
+fn prompt_choice(left_label: &str, right_label: &str) -> Result<Choice> {
+    let (left_key, left_rest) = prompt_key(left_label);
+    let (right_key, right_rest) = prompt_key(right_label);
     loop {
-        print!("Choose [s]kill or [a]gents for this hunk: ");
+        print!("Choose [{left_key}]{left_rest}, [{right_key}]{right_rest}, [b]oth, or [e]dit for this hunk: ");
         io::stdout().flush().ok();
         let mut input = String::new();
         let read = io::stdin().read_line(&mut input)?;
         if read == 0 {
             bail!("stdin closed during conflict resolution");
         }
-        match input.trim().to_ascii_lowercase().as_str() {
-            "s" | "skill" => return Ok(Choice::Skill),
-            "a" | "agents" => return Ok(Choice::Agents),
+        let input = input.trim().to_ascii_lowercase();
+        let is_left = (input.len() == 1 && input.chars().next() == Some(left_key)) || input == left_label.to_ascii_lowercase();
+        let is_right = (input.len() == 1 && input.chars().next() == Some(right_key)) || input == right_label.to_ascii_lowercase();
+        if is_left {
+            return Ok(Choice::Left);
+        }
+        if is_right {
+            return Ok(Choice::Right);
+        }
+        match input.as_str() {
+            "b" | "both" => return Ok(Choice::Both),
+            "e" | "edit" => return Ok(Choice::Edit),
             _ => {
-                println!("Enter 's' or 'a'.");
+                println!("Enter '{left_key}', '{right_key}', 'b', or 'e'.");
+            }
+        }
+    }
+}
+
+/// Render a unified diff between `old` and `new`, colorizing added/removed lines when `color` is
+/// true. Used by the `diff` subcommand; built on the same `render_unified_hunk` that `sync`'s
+/// conflict prompts use.
+pub(crate) fn render_diff(old: &str, new: &str, color: bool) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let mut out = String::new();
+    for group in diff.grouped_ops(3) {
+        let hunk = render_unified_hunk(&diff, &group);
+        if !color {
+            out.push_str(&hunk);
+            continue;
+        }
+        for line in hunk.lines() {
+            if let Some(rest) = line.strip_prefix('-') {
+                out.push_str(&format!("\x1b[31m-{rest}\x1b[0m\n"));
+            } else if let Some(rest) = line.strip_prefix('+') {
+                out.push_str(&format!("\x1b[32m+{rest}\x1b[0m\n"));
+            } else {
+                out.push_str(line);
+                out.push('\n');
             }
         }
     }
+    out
 }
 
-fn normalize_content(content: &str) -> String {
+pub(crate) fn normalize_content(content: &str) -> String {
     content.replace("\r\n", "\n").trim_end_matches('\n').to_string()
 }
 
@@ -221,75 +638,6 @@ fn print_sync_status(skills_store: &SkillsStore, agents_doc: Option<&AgentsDoc>)
     Ok(())
 }
 
-fn git_pull_rebase(root: &Path) -> Result<()> {
-    if !git_is_repo(root)? {
-        return Ok(());
-    }
-    let status = Command::new("git")
-        .arg("-C")
-        .arg(root)
-        .arg("pull")
-        .arg("--rebase")
-        .status()
-        .context("failed to run git pull --rebase")?;
-    if !status.success() {
-        bail!("git pull --rebase failed");
-    }
-    Ok(())
-}
-
-fn commit_skills_repo(root: &Path) -> Result<()> {
-    if !git_is_repo(root)? {
-        return Ok(());
-    }
-    let status = Command::new("git")
-        .arg("-C")
-        .arg(root)
-        .arg("add")
-        .arg("-A")
-        .status()
-        .context("failed to run git add")?;
-    if !status.success() {
-        bail!("git add failed");
-    }
-    if git_is_clean(root)? {
-        return Ok(());
-    }
-    let status = Command::new("git")
-        .arg("-C")
-        .arg(root)
-        .arg("commit")
-        .arg("-m")
-        .arg("Update skills")
-        .status()
-        .context("failed to run git commit")?;
-    if !status.success() {
-        bail!("git commit failed");
-    }
-    Ok(())
-}
-
-fn git_is_repo(root: &Path) -> Result<bool> {
-    let output = Command::new("git")
-        .arg("-C")
-        .arg(root)
-        .arg("rev-parse")
-        .arg("--is-inside-work-tree")
-        .output()
-        .context("failed to run git rev-parse")?;
-    Ok(output.status.success())
-}
-
-fn git_is_clean(root: &Path) -> Result<bool> {
-    let output = Command::new("git")
-        .arg("-C")
-        .arg(root)
-        .arg("status")
-        .arg("--porcelain")
-        .output()
-        .context("failed to run git status")?;
-    if !output.status.success() {
-        bail!("git status failed");
-    }
-    Ok(output.stdout.is_empty())
+fn commit_skills_repo(backend: &dyn VcsBackend, root: &Path) -> Result<()> {
+    backend.commit_all(root, "Update skills")
 }
@@ -0,0 +1,202 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// One `{{ variable }}` placeholder declared for a skill.
+#[derive(Debug, Deserialize)]
+pub struct VariableSpec {
+    pub name: String,
+    pub description: Option<String>,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct VariableManifest {
+    #[serde(default)]
+    variables: Vec<VariableSpec>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ResolvedCache {
+    #[serde(flatten)]
+    values: HashMap<String, String>,
+}
+
+/// Resolve `{{ variable }}` placeholders in `content` and substitute them, consulting the
+/// `<name>.vars.toml` manifest (if any) and caching newly-resolved answers in
+/// `<name>.vars.resolved.toml`.
+///
+/// # Errors
+///
+/// Returns an error if a variable has no resolvable value and the process is non-interactive.
+pub fn render(
+    skills_dir: &Path,
+    name: &str,
+    content: &str,
+    cli_vars: &HashMap<String, String>,
+    config_overrides: &HashMap<String, String>,
+    refresh: bool,
+) -> Result<String> {
+    let manifest = load_manifest(skills_dir, name)?;
+    if manifest.variables.is_empty() {
+        return Ok(content.to_string());
+    }
+
+    if refresh {
+        clear_cache(skills_dir, name)?;
+    }
+    let mut cache = load_cache(skills_dir, name)?;
+    let mut cache_changed = false;
+
+    for spec in &manifest.variables {
+        if cache.values.contains_key(&spec.name) && !cli_vars.contains_key(&spec.name) {
+            continue;
+        }
+        let value = resolve_one(spec, cli_vars, config_overrides)?;
+        cache.values.insert(spec.name.clone(), value);
+        cache_changed = true;
+    }
+
+    if cache_changed {
+        save_cache(skills_dir, name, &cache)?;
+    }
+
+    Ok(substitute(content, &cache.values))
+}
+
+/// Delete the cached resolved values for a skill, so the next `render` call re-resolves them.
+///
+/// # Errors
+///
+/// Returns an error if the cache file exists but cannot be removed.
+pub fn clear_cache(skills_dir: &Path, name: &str) -> Result<()> {
+    let path = resolved_cache_path(skills_dir, name);
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("failed to remove '{}'", path.display()))?;
+    }
+    Ok(())
+}
+
+fn resolve_one(
+    spec: &VariableSpec,
+    cli_vars: &HashMap<String, String>,
+    config_overrides: &HashMap<String, String>,
+) -> Result<String> {
+    if let Some(value) = cli_vars.get(&spec.name) {
+        return Ok(value.clone());
+    }
+    if let Some(value) = config_overrides.get(&spec.name) {
+        return Ok(value.clone());
+    }
+    if let Ok(value) = std::env::var(&spec.name) {
+        return Ok(value);
+    }
+    if let Some(default) = &spec.default {
+        return Ok(default.clone());
+    }
+    if io::stdin().is_terminal() {
+        return prompt_for(spec);
+    }
+    bail!(
+        "variable '{}' is not resolvable in non-interactive mode; pass --var {}=value",
+        spec.name,
+        spec.name
+    );
+}
+
+fn prompt_for(spec: &VariableSpec) -> Result<String> {
+    let description = spec.description.as_deref().unwrap_or("");
+    print!("Value for '{}' ({description}): ", spec.name);
+    io::stdout().flush().ok();
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .with_context(|| format!("failed to read value for '{}'", spec.name))?;
+    Ok(input.trim().to_string())
+}
+
+/// Replace `{{ name }}` tokens with their resolved value, trimming interior whitespace. Unknown
+/// tokens are left intact and a warning is emitted to stderr.
+fn substitute(content: &str, values: &HashMap<String, String>) -> String {
+    let token_re = Regex::new(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}").expect("valid template regex");
+    token_re
+        .replace_all(content, |captures: &regex::Captures<'_>| {
+            let name = &captures[1];
+            values.get(name).cloned().unwrap_or_else(|| {
+                eprintln!("warning: unresolved template variable '{{{{ {name} }}}}'");
+                captures[0].to_string()
+            })
+        })
+        .to_string()
+}
+
+fn load_manifest(skills_dir: &Path, name: &str) -> Result<VariableManifest> {
+    let path = manifest_path(skills_dir, name);
+    if !path.exists() {
+        return Ok(VariableManifest::default());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read '{}'", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("failed to parse '{}'", path.display()))
+}
+
+fn load_cache(skills_dir: &Path, name: &str) -> Result<ResolvedCache> {
+    let path = resolved_cache_path(skills_dir, name);
+    if !path.exists() {
+        return Ok(ResolvedCache::default());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read '{}'", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("failed to parse '{}'", path.display()))
+}
+
+fn save_cache(skills_dir: &Path, name: &str, cache: &ResolvedCache) -> Result<()> {
+    let path = resolved_cache_path(skills_dir, name);
+    let contents = toml::to_string_pretty(cache).context("failed to serialize resolved variables")?;
+    std::fs::write(&path, contents).with_context(|| format!("failed to write '{}'", path.display()))
+}
+
+fn manifest_path(skills_dir: &Path, name: &str) -> PathBuf {
+    skills_dir.join(format!("{name}.vars.toml"))
+}
+
+fn resolved_cache_path(skills_dir: &Path, name: &str) -> PathBuf {
+    skills_dir.join(format!("{name}.vars.resolved.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_known_tokens_and_trims_whitespace() {
+        let mut values = HashMap::new();
+        values.insert("endpoint".to_string(), "https://api.example.com".to_string());
+        let rendered = substitute("Call {{  endpoint }} for data", &values);
+        assert_eq!(rendered, "Call https://api.example.com for data");
+    }
+
+    #[test]
+    fn leaves_unknown_tokens_intact() {
+        let values = HashMap::new();
+        let rendered = substitute("Call {{ endpoint }}", &values);
+        assert_eq!(rendered, "Call {{ endpoint }}");
+    }
+
+    #[test]
+    fn resolves_from_cli_vars_before_default() {
+        let mut cli_vars = HashMap::new();
+        cli_vars.insert("project".to_string(), "capybara".to_string());
+        let spec = VariableSpec {
+            name: "project".to_string(),
+            description: None,
+            default: Some("fallback".to_string()),
+        };
+        let value = resolve_one(&spec, &cli_vars, &HashMap::new()).expect("resolve");
+        assert_eq!(value, "capybara");
+    }
+}
@@ -0,0 +1,263 @@
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Environment variable selecting which [`VcsBackend`] `backend_from_env` returns. Set to `mock`
+/// to run the sync test suite without a `git` executable; any other value (or unset) uses
+/// [`GitBackend`].
+const BACKEND_ENV_VAR: &str = "PRIME_AGENT_VCS_BACKEND";
+
+/// Version-control operations `sync`/`sync-remote` need, abstracted so a real `git` checkout isn't
+/// required to exercise the rest of the sync logic in tests. The skill store is just a directory
+/// tree, so a `MercurialBackend` (or any other VCS) can implement this the same way.
+pub trait VcsBackend {
+    /// Whether `root` is inside a working tree this backend manages.
+    fn is_repo(&self, root: &Path) -> Result<bool>;
+    /// Stage every change under `root` and commit it with `message`, if there is anything to
+    /// commit. A no-op (not an error) when the tree is already clean.
+    fn commit_all(&self, root: &Path, message: &str) -> Result<()>;
+    /// Pull (rebasing local commits on top) from the configured remote.
+    fn pull(&self, root: &Path) -> Result<()>;
+    /// Push the current branch to the configured remote.
+    fn push(&self, root: &Path) -> Result<()>;
+    /// The content of `path` as of the last commit, or `None` if it has no committed history yet.
+    fn last_committed_content(&self, root: &Path, path: &Path) -> Result<Option<String>>;
+}
+
+/// Select a [`VcsBackend`] based on [`BACKEND_ENV_VAR`], defaulting to [`GitBackend`].
+#[must_use]
+pub fn backend_from_env() -> Box<dyn VcsBackend> {
+    match std::env::var(BACKEND_ENV_VAR) {
+        Ok(value) if value.eq_ignore_ascii_case("mock") => Box::new(MockBackend::repo()),
+        _ => Box::new(GitBackend),
+    }
+}
+
+/// Real backend that shells out to the `git` binary on `PATH`.
+pub struct GitBackend;
+
+impl VcsBackend for GitBackend {
+    fn is_repo(&self, root: &Path) -> Result<bool> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .arg("rev-parse")
+            .arg("--is-inside-work-tree")
+            .output()
+            .context("failed to run git rev-parse")?;
+        Ok(output.status.success())
+    }
+
+    fn commit_all(&self, root: &Path, message: &str) -> Result<()> {
+        if !self.is_repo(root)? {
+            return Ok(());
+        }
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .arg("add")
+            .arg("-A")
+            .status()
+            .context("failed to run git add")?;
+        if !status.success() {
+            bail!("git add failed");
+        }
+        if self.is_clean(root)? {
+            return Ok(());
+        }
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .arg("commit")
+            .arg("-m")
+            .arg(message)
+            .status()
+            .context("failed to run git commit")?;
+        if !status.success() {
+            bail!("git commit failed");
+        }
+        Ok(())
+    }
+
+    fn pull(&self, root: &Path) -> Result<()> {
+        if !self.is_repo(root)? {
+            return Ok(());
+        }
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .arg("pull")
+            .arg("--rebase")
+            .status()
+            .context("failed to run git pull --rebase")?;
+        if !status.success() {
+            bail!("git pull --rebase failed");
+        }
+        Ok(())
+    }
+
+    fn push(&self, root: &Path) -> Result<()> {
+        if !self.is_repo(root)? {
+            return Ok(());
+        }
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .arg("push")
+            .status()
+            .context("failed to run git push")?;
+        if !status.success() {
+            bail!("git push failed");
+        }
+        Ok(())
+    }
+
+    fn last_committed_content(&self, root: &Path, path: &Path) -> Result<Option<String>> {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .arg("show")
+            .arg(format!("HEAD:{}", relative.display()))
+            .output()
+            .context("failed to run git show")?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+    }
+}
+
+impl GitBackend {
+    fn is_clean(&self, root: &Path) -> Result<bool> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .arg("status")
+            .arg("--porcelain")
+            .output()
+            .context("failed to run git status")?;
+        if !output.status.success() {
+            bail!("git status failed");
+        }
+        Ok(output.stdout.is_empty())
+    }
+}
+
+/// One call recorded by [`MockBackend`], in invocation order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockInvocation {
+    IsRepo,
+    CommitAll(String),
+    Pull,
+    Push,
+    LastCommittedContent(std::path::PathBuf),
+}
+
+/// In-memory backend for tests: records every call it receives instead of touching the
+/// filesystem or spawning `git`, and returns canned answers configured up front.
+#[derive(Default)]
+pub struct MockBackend {
+    invocations: Mutex<Vec<MockInvocation>>,
+    is_repo: bool,
+    committed_content: std::collections::HashMap<std::path::PathBuf, String>,
+}
+
+impl MockBackend {
+    /// A mock backend that reports `root` as a repo with no committed history.
+    #[must_use]
+    pub fn repo() -> Self {
+        Self {
+            invocations: Mutex::new(Vec::new()),
+            is_repo: true,
+            committed_content: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Seed the content [`VcsBackend::last_committed_content`] should return for `path`.
+    pub fn seed_committed_content(&mut self, path: std::path::PathBuf, content: String) {
+        self.committed_content.insert(path, content);
+    }
+
+    /// The calls made to this backend so far, oldest first.
+    #[must_use]
+    pub fn invocations(&self) -> Vec<MockInvocation> {
+        self.invocations.lock().unwrap_or_else(std::sync::PoisonError::into_inner).clone()
+    }
+
+    fn record(&self, invocation: MockInvocation) {
+        self.invocations
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(invocation);
+    }
+}
+
+impl VcsBackend for MockBackend {
+    fn is_repo(&self, _root: &Path) -> Result<bool> {
+        self.record(MockInvocation::IsRepo);
+        Ok(self.is_repo)
+    }
+
+    fn commit_all(&self, _root: &Path, message: &str) -> Result<()> {
+        self.record(MockInvocation::CommitAll(message.to_string()));
+        Ok(())
+    }
+
+    fn pull(&self, _root: &Path) -> Result<()> {
+        self.record(MockInvocation::Pull);
+        Ok(())
+    }
+
+    fn push(&self, _root: &Path) -> Result<()> {
+        self.record(MockInvocation::Push);
+        Ok(())
+    }
+
+    fn last_committed_content(&self, _root: &Path, path: &Path) -> Result<Option<String>> {
+        self.record(MockInvocation::LastCommittedContent(path.to_path_buf()));
+        Ok(self.committed_content.get(path).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_backend_records_invocations_in_order() {
+        let backend = MockBackend::repo();
+        backend.is_repo(Path::new("/tmp/x")).expect("is_repo");
+        backend.commit_all(Path::new("/tmp/x"), "Update skills").expect("commit_all");
+        backend.pull(Path::new("/tmp/x")).expect("pull");
+
+        assert_eq!(
+            backend.invocations(),
+            vec![
+                MockInvocation::IsRepo,
+                MockInvocation::CommitAll("Update skills".to_string()),
+                MockInvocation::Pull,
+            ]
+        );
+    }
+
+    #[test]
+    fn mock_backend_returns_seeded_committed_content() {
+        let mut backend = MockBackend::repo();
+        let path = std::path::PathBuf::from("alpha/SKILL.md");
+        backend.seed_committed_content(path.clone(), "Initial\n".to_string());
+
+        let content = backend.last_committed_content(Path::new("/tmp/x"), &path).expect("content");
+        assert_eq!(content.as_deref(), Some("Initial\n"));
+    }
+
+    #[test]
+    fn backend_from_env_selects_mock_when_requested() {
+        std::env::set_var(BACKEND_ENV_VAR, "mock");
+        let backend = backend_from_env();
+        let recorded = backend.is_repo(Path::new("/tmp/x")).expect("is_repo");
+        assert!(recorded);
+        std::env::remove_var(BACKEND_ENV_VAR);
+    }
+}
@@ -564,6 +564,35 @@ fn sync_commits_skills_repo() {
     assert_eq!(count.trim(), "2");
 }
 
+/// Same sync path as `sync_commits_skills_repo`, but with `PRIME_AGENT_VCS_BACKEND=mock` and no
+/// `git` repository on disk at all, demonstrating the suite can exercise sync without a `git`
+/// executable.
+#[test]
+fn sync_commits_skills_repo_with_mock_vcs_backend() {
+    let temp = TempDir::new().expect("temp dir");
+    let skills_dir = temp.path().join("skills");
+    write_config(&temp, &skills_dir);
+    fs::create_dir_all(skills_dir.join("alpha")).expect("alpha dir");
+    fs::write(skills_dir.join("alpha/SKILL.md"), "Initial\n").expect("skill");
+
+    let agents = [
+        "<!-- prime-agent(Start alpha) -->",
+        "## alpha",
+        "Updated content",
+        "<!-- prime-agent(End alpha) -->",
+        "",
+    ]
+    .join("\n");
+    fs::write(default_agents_path(&temp), agents).expect("agents");
+
+    let mut cmd = cmd_with_skills_dir(&temp, &skills_dir);
+    cmd.env("PRIME_AGENT_VCS_BACKEND", "mock");
+    cmd.arg("sync").write_stdin("a\n");
+    cmd.assert().success();
+
+    assert!(!skills_dir.join(".git").exists());
+}
+
 #[test]
 fn list_with_fragment_outputs_single_line() {
     let temp = TempDir::new().expect("temp dir");
@@ -675,3 +704,31 @@ fn sync_remote_commits_and_pulls() {
     cmd.arg("sync-remote").write_stdin("a\n");
     cmd.assert().success();
 }
+
+/// Same command as `sync_remote_commits_and_pulls`, but with `PRIME_AGENT_VCS_BACKEND=mock` and no
+/// git remote set up at all: the mock backend no-ops `pull`, so this exercises `sync-remote`
+/// without a `git` executable.
+#[test]
+fn sync_remote_commits_and_pulls_with_mock_vcs_backend() {
+    let temp = TempDir::new().expect("temp dir");
+    let skills_dir = temp.path().join("skills");
+    write_config(&temp, &skills_dir);
+
+    fs::create_dir_all(skills_dir.join("alpha")).expect("alpha dir");
+    fs::write(skills_dir.join("alpha/SKILL.md"), "Initial\n").expect("skill");
+
+    let agents = [
+        "<!-- prime-agent(Start alpha) -->",
+        "## alpha",
+        "Updated content",
+        "<!-- prime-agent(End alpha) -->",
+        "",
+    ]
+    .join("\n");
+    fs::write(default_agents_path(&temp), agents).expect("agents");
+
+    let mut cmd = cmd_with_skills_dir(&temp, &skills_dir);
+    cmd.env("PRIME_AGENT_VCS_BACKEND", "mock");
+    cmd.arg("sync-remote").write_stdin("a\n");
+    cmd.assert().success();
+}